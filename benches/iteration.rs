@@ -0,0 +1,85 @@
+use a1_notation::{new, RangeOrCell};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::str::FromStr;
+
+fn range(c: &mut Criterion) {
+    let r = RangeOrCell::from_str("A1:Z40000").unwrap();
+    let mut group = c.benchmark_group("A1:Z40000");
+
+    group.bench_function("iter", |b| {
+        b.iter(|| {
+            black_box(&r).iter().fold(0, |n, r| match r {
+                RangeOrCell::Cell(a) => n ^ a.column.x,
+                _ => n,
+            })
+        })
+    });
+    group.bench_function("addresses", |b| {
+        b.iter(|| black_box(&r).addresses().fold(0, |n, a| n ^ a.column.x))
+    });
+    group.bench_function("coordinates", |b| {
+        b.iter(|| black_box(&r).coordinates().fold(0, |n, (y, x)| n ^ y ^ x))
+    });
+    group.finish();
+}
+
+fn a1_with_sheet_name(c: &mut Criterion) {
+    let a1 = new("'Quarterly Report'!A1:Z40000").unwrap();
+    let mut group = c.benchmark_group("'Quarterly Report'!A1:Z40000");
+
+    group.bench_function("iter", |b| {
+        b.iter(|| {
+            black_box(&a1)
+                .iter()
+                .fold(0, |n, a1| n ^ a1.sheet_name.map_or(0, |s| s.len()))
+        })
+    });
+    group.bench_function("addresses", |b| {
+        b.iter(|| black_box(&a1).addresses().fold(0, |n, a| n ^ a.column.x))
+    });
+    group.finish();
+}
+
+fn non_contiguous(c: &mut Criterion) {
+    let r = RangeOrCell::from_str("A1:J1000,L1:L1000,N5,P1:Z500").unwrap();
+    let mut group = c.benchmark_group("A1:J1000,L1:L1000,N5,P1:Z500");
+
+    group.bench_function("iter", |b| {
+        b.iter(|| {
+            black_box(&r).iter().fold(0, |n, r| match r {
+                RangeOrCell::Cell(a) => n ^ a.column.x,
+                _ => n,
+            })
+        })
+    });
+    group.bench_function("addresses", |b| {
+        b.iter(|| black_box(&r).addresses().fold(0, |n, a| n ^ a.column.x))
+    });
+    group.finish();
+}
+
+fn overlapping(c: &mut Criterion) {
+    let r = RangeOrCell::from_str("A1:Z20000,M10000:AL30000,A15000:AL15000").unwrap();
+    let mut group = c.benchmark_group("A1:Z20000,M10000:AL30000,A15000:AL15000");
+
+    group.bench_function("addresses", |b| {
+        b.iter(|| black_box(&r).addresses().fold(0, |n, a| n ^ a.column.x))
+    });
+    group.bench_function("unique_addresses", |b| {
+        b.iter(|| {
+            black_box(&r)
+                .unique_addresses()
+                .fold(0, |n, a| n ^ a.column.x)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    range,
+    a1_with_sheet_name,
+    non_contiguous,
+    overlapping
+);
+criterion_main!(benches);
@@ -0,0 +1,94 @@
+use crate::A1;
+
+impl A1 {
+    /// The next step of the F4 cycle, keeping the `sheet_name`.  See
+    /// `RangeOrCell::cycle_absolute`.
+    pub fn cycle_absolute(&self) -> Self {
+        Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.cycle_absolute(),
+        }
+    }
+
+    /// Make every part of the reference absolute.  See `RangeOrCell::make_absolute`.
+    pub fn make_absolute(&self) -> Self {
+        Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.make_absolute(),
+        }
+    }
+
+    /// Make every part of the reference relative.  See `RangeOrCell::make_relative`.
+    pub fn make_relative(&self) -> Self {
+        Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.make_relative(),
+        }
+    }
+
+    /// Set whether both ends of the reference are `absolute`, per axis.  See
+    /// `RangeOrCell::with_absolute`.
+    pub fn with_absolute(&self, column: Option<bool>, row: Option<bool>) -> Self {
+        Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.with_absolute(column, row),
+        }
+    }
+
+    /// Set whether the `from` end of the reference is `absolute`, per axis.  See
+    /// `RangeOrCell::with_from_absolute`.
+    pub fn with_from_absolute(&self, column: Option<bool>, row: Option<bool>) -> Self {
+        Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.with_from_absolute(column, row),
+        }
+    }
+
+    /// Set whether the `to` end of the reference is `absolute`, per axis.  See
+    /// `RangeOrCell::with_to_absolute`.
+    pub fn with_to_absolute(&self, column: Option<bool>, row: Option<bool>) -> Self {
+        Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.with_to_absolute(column, row),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn cycle_absolute() {
+        assert_eq!(
+            new("Foo!A1:B2").unwrap().cycle_absolute().to_string(),
+            "Foo!$A$1:$B$2"
+        );
+    }
+
+    #[test]
+    fn make_absolute_and_relative() {
+        let a1 = new("Foo!A$1:$B2").unwrap();
+
+        assert_eq!(a1.make_absolute().to_string(), "Foo!$A$1:$B$2");
+        assert_eq!(a1.make_relative().to_string(), "Foo!A1:B2");
+    }
+
+    #[test]
+    fn with_absolute() {
+        let a1 = new("Foo!A1:B2").unwrap();
+
+        assert_eq!(
+            a1.with_absolute(Some(true), None).to_string(),
+            "Foo!$A1:$B2"
+        );
+        assert_eq!(
+            a1.with_from_absolute(None, Some(true)).to_string(),
+            "Foo!A$1:B2"
+        );
+        assert_eq!(
+            a1.with_to_absolute(Some(true), Some(true)).to_string(),
+            "Foo!A1:$B$2"
+        );
+    }
+}
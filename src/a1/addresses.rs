@@ -0,0 +1,64 @@
+use crate::range_or_cell::addresses::{Addresses, Coordinates};
+use crate::range_or_cell::unique::UniqueAddresses;
+use crate::{Traversal, A1};
+
+impl A1 {
+    /// Every cell of the reference as a plain `Address`, without copying the `sheet_name` for
+    /// each one.  See `RangeOrCell::addresses`.
+    pub fn addresses(&self) -> Addresses<'_> {
+        self.reference.addresses()
+    }
+
+    /// Every cell of the reference in the given `traversal` order.  See
+    /// `RangeOrCell::addresses_with`.
+    pub fn addresses_with(&self, traversal: Traversal) -> Addresses<'_> {
+        self.reference.addresses_with(traversal)
+    }
+
+    /// The `(row, column)` indexes of every cell of the reference.  See
+    /// `RangeOrCell::coordinates`.
+    pub fn coordinates(&self) -> Coordinates<'_> {
+        self.reference.coordinates()
+    }
+
+    /// Every distinct cell of the reference, visiting the overlaps of a `NonContiguous` once.
+    /// See `RangeOrCell::unique_addresses`.
+    pub fn unique_addresses(&self) -> UniqueAddresses {
+        self.reference.unique_addresses()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn addresses() {
+        let a1 = new("Foo!A1:B2").unwrap();
+
+        assert_eq!(
+            a1.addresses().map(|a| a.to_string()).collect::<Vec<_>>(),
+            vec!["A1", "B1", "A2", "B2"]
+        );
+        assert!(a1
+            .addresses()
+            .zip(a1.iter())
+            .all(|(a, b)| b.reference == RangeOrCell::Cell(a)));
+    }
+
+    #[test]
+    fn coordinates() {
+        assert_eq!(
+            new("Foo!B2:B3").unwrap().coordinates().collect::<Vec<_>>(),
+            vec![(1, 1), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn unique_addresses() {
+        assert_eq!(
+            new("Foo!A1:B1,B1:C1").unwrap().unique_addresses().count(),
+            3
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use crate::range_or_cell::border::RangeOrCellBorder;
+use crate::{Connectivity, Direction, A1};
+use std::iter;
+
+/// A thin wrapper around `RangeOrCellBorder` which also reflects the `sheet_name` of the `A1`.
+pub struct A1Border {
+    sheet_name: Option<String>,
+    border: RangeOrCellBorder,
+}
+
+impl A1 {
+    /// The row or column of cells along one side of the reference.  See `RangeOrCell::edge`.
+    pub fn edge(&self, direction: Direction) -> Option<Self> {
+        Some(Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.edge(direction)?,
+        })
+    }
+
+    /// The cells along the inside of the border.  See `RangeOrCell::perimeter`.
+    pub fn perimeter(&self) -> A1Border {
+        A1Border {
+            sheet_name: self.sheet_name.clone(),
+            border: self.reference.perimeter(),
+        }
+    }
+
+    /// The cells just outside of the border.  See `RangeOrCell::ring`.
+    pub fn ring(&self, connectivity: Connectivity) -> A1Border {
+        A1Border {
+            sheet_name: self.sheet_name.clone(),
+            border: self.reference.ring(connectivity),
+        }
+    }
+}
+
+impl iter::Iterator for A1Border {
+    type Item = A1;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(A1 {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.border.next()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn edge() {
+        assert_eq!(
+            new("Foo!A1:C3").unwrap().edge(Direction::Left),
+            Some(new("Foo!A1:A3").unwrap())
+        );
+    }
+
+    #[test]
+    fn perimeter() {
+        assert_eq!(
+            new("Foo!A1:B2")
+                .unwrap()
+                .perimeter()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["Foo!A1", "Foo!B1", "Foo!B2", "Foo!A2"]
+        );
+    }
+
+    #[test]
+    fn ring() {
+        assert_eq!(
+            new("Foo!A1")
+                .unwrap()
+                .ring(Connectivity::Eight)
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["Foo!B1", "Foo!B2", "Foo!A2"]
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Reading the values of a reference out of a workbook with
+//! [calamine](https://docs.rs/calamine) (behind the `calamine` feature).  The reference's sheet
+//! is the one that's read (or the first sheet if it doesn't have one) and calamine's zero-based
+//! `(row, column)` positions line up with `Address`es.
+use super::A1;
+use crate::rect::Rect;
+use crate::sheet_name;
+use crate::{Address, Error, Grid, Index, RangeOrCell, Result, SparseGrid};
+use calamine::{Data, Range, Reader, Sheets};
+use std::io;
+
+impl A1 {
+    /// Read the cells of the reference which have something in them.  Any kind of reference
+    /// works - a `ColumnRange` or `RowRange` is read as far as the sheet's used range goes.
+    ///
+    /// Returns an `Error::MissingSheetError` if the sheet isn't in the workbook and an
+    /// `Error::IoError` if it can't be read.
+    ///
+    /// ```no_run
+    /// use a1_notation::A1;
+    /// use std::str::FromStr;
+    ///
+    /// let mut workbook = calamine::open_workbook_auto("report.xlsx").unwrap();
+    /// let cells = A1::from_str("Totals!B:B").unwrap().read_cells(&mut workbook).unwrap();
+    /// for (address, value) in cells.iter() {
+    ///     println!("{address} = {value}");
+    /// }
+    /// ```
+    pub fn read_cells<RS: io::Read + io::Seek>(
+        &self,
+        workbook: &mut Sheets<RS>,
+    ) -> Result<SparseGrid<Data>> {
+        let sheet = self.worksheet_range(workbook)?;
+        let mut cells = SparseGrid::new();
+
+        let Some(used) = used_rect(&sheet) else {
+            return Ok(cells);
+        };
+        for rect in self.reference.rects() {
+            let Some(rect) = rect.intersection(&used) else {
+                continue;
+            };
+            for y in rect.top..=rect.bottom {
+                for x in rect.left..=rect.right {
+                    if let Some(value) = value_at(&sheet, x, y).filter(|v| **v != Data::Empty) {
+                        cells.set(Address::new(x, y), value.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(cells)
+    }
+
+    /// Read the reference as a block of values, with its top-left cell at `A1` of the `Grid`.
+    /// Cells without anything in them are `Data::Empty` and a `ColumnRange` or `RowRange` stops
+    /// at the edge of the sheet's used range.
+    ///
+    /// Returns an `Error::InvalidArgumentError` for a `NonContiguous` (use `read_cells` for
+    /// those), otherwise the same errors as `read_cells`.
+    pub fn read_range<RS: io::Read + io::Seek>(
+        &self,
+        workbook: &mut Sheets<RS>,
+    ) -> Result<Grid<Data>> {
+        if let RangeOrCell::NonContiguous(_) = self.reference {
+            return Err(Error::invalid_argument(
+                self,
+                "Only a contiguous reference can be read as a range",
+            ));
+        }
+
+        let sheet = self.worksheet_range(workbook)?;
+        let rect = self.reference.rects()[0];
+
+        // whole rows and columns only go as far as the used range does
+        let end = sheet.end();
+        let right = clip(rect.right, end.map(|(_, column)| column as Index));
+        let bottom = clip(rect.bottom, end.map(|(row, _)| row as Index));
+        let (Some(right), Some(bottom)) = (right, bottom) else {
+            return Ok(Grid::new());
+        };
+        if right < rect.left || bottom < rect.top {
+            return Ok(Grid::new());
+        }
+
+        Ok(Grid::from(
+            (rect.top..=bottom)
+                .map(|y| {
+                    (rect.left..=right)
+                        .map(|x| value_at(&sheet, x, y).cloned().unwrap_or_default())
+                        .collect()
+                })
+                .collect::<Vec<Vec<_>>>(),
+        ))
+    }
+
+    fn worksheet_range<RS: io::Read + io::Seek>(
+        &self,
+        workbook: &mut Sheets<RS>,
+    ) -> Result<Range<Data>> {
+        // like references, the workbook's sheet names are case-insensitive
+        let sheet_names = workbook.sheet_names();
+        let sheet_name = match &self.sheet_name {
+            Some(name) => sheet_names
+                .iter()
+                .find(|s| sheet_name::is_same(s, name))
+                .ok_or_else(|| {
+                    Error::missing_sheet(
+                        self,
+                        format!("The workbook doesn't have a sheet named `{name}`"),
+                    )
+                })?,
+            None => sheet_names.first().ok_or_else(|| {
+                Error::missing_sheet(self, "The workbook doesn't have any sheets")
+            })?,
+        };
+
+        workbook
+            .worksheet_range(sheet_name)
+            .map_err(Error::io_error)
+    }
+}
+
+/// The sheet's used range as a `Rect`, if there's anything on it
+fn used_rect(sheet: &Range<Data>) -> Option<Rect> {
+    let ((top, left), (bottom, right)) = sheet.start().zip(sheet.end())?;
+
+    Some(Rect {
+        left: left as Index,
+        top: top as Index,
+        right: right as Index,
+        bottom: bottom as Index,
+    })
+}
+
+/// An unbounded edge of a whole row or column stops at the end of the used range instead (and
+/// there isn't one if nothing is used)
+fn clip(edge: Index, end: Option<Index>) -> Option<Index> {
+    if edge == Index::MAX {
+        end
+    } else {
+        Some(edge)
+    }
+}
+
+fn value_at(sheet: &Range<Data>, x: Index, y: Index) -> Option<&Data> {
+    sheet.get_value((u32::try_from(y).ok()?, u32::try_from(x).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use calamine::{open_workbook_auto_from_rs, Data, Sheets};
+    use rust_xlsxwriter::Workbook;
+    use std::io::Cursor;
+    use std::str::FromStr;
+
+    /// A workbook with an empty first sheet and a "Data" sheet which has B2:C3 filled in
+    fn workbook() -> Sheets<Cursor<Vec<u8>>> {
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet().set_name("Empty").unwrap();
+        let sheet = workbook.add_worksheet().set_name("Data").unwrap();
+        sheet.write(1, 1, "b2").unwrap();
+        sheet.write(1, 2, 3).unwrap();
+        sheet.write(2, 1, true).unwrap();
+        sheet.write(2, 2, "c3").unwrap();
+
+        open_workbook_auto_from_rs(Cursor::new(workbook.save_to_buffer().unwrap())).unwrap()
+    }
+
+    fn a1(s: &str) -> A1 {
+        A1::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn read_cells() {
+        let cells = a1("Data!A1:B3,C3").read_cells(&mut workbook()).unwrap();
+
+        assert_eq!(
+            cells
+                .iter()
+                .map(|(a, v)| (a.to_string(), v.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("B2".to_string(), Data::String("b2".to_string())),
+                ("B3".to_string(), Data::Bool(true)),
+                ("C3".to_string(), Data::String("c3".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_cells_column_range() {
+        let cells = a1("Data!C:C").read_cells(&mut workbook()).unwrap();
+
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells.get_a1("C2").unwrap(), Some(&Data::Float(3.0)));
+    }
+
+    #[test]
+    fn read_cells_first_sheet() {
+        assert!(a1("A1:Z100")
+            .read_cells(&mut workbook())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn read_range() {
+        assert_eq!(
+            a1("Data!A2:C3")
+                .read_range(&mut workbook())
+                .unwrap()
+                .into_rows(),
+            vec![
+                vec![
+                    Data::Empty,
+                    Data::String("b2".to_string()),
+                    Data::Float(3.0)
+                ],
+                vec![
+                    Data::Empty,
+                    Data::Bool(true),
+                    Data::String("c3".to_string())
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn read_range_row_range() {
+        assert_eq!(
+            a1("Data!3:3")
+                .read_range(&mut workbook())
+                .unwrap()
+                .into_rows(),
+            vec![vec![
+                Data::Empty,
+                Data::Bool(true),
+                Data::String("c3".to_string())
+            ]]
+        );
+        assert!(a1("Empty!A:B")
+            .read_range(&mut workbook())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn read_errors() {
+        assert!(matches!(
+            a1("Data!A1,B2").read_range(&mut workbook()),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+    }
+
+    #[test]
+    fn read_missing_sheet() {
+        assert_eq!(
+            a1("Missing!A1").read_cells(&mut workbook()),
+            Err(Error::MissingSheetError {
+                reference: "Missing!A1".to_string(),
+                message: "The workbook doesn't have a sheet named `Missing`".to_string(),
+            })
+        );
+        assert!(matches!(
+            a1("Missing!A1:B2").read_range(&mut workbook()),
+            Err(Error::MissingSheetError { .. })
+        ));
+    }
+
+    #[test]
+    fn read_sheet_name_case_insensitive() {
+        assert_eq!(a1("data!C:C").read_cells(&mut workbook()).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::range_or_cell::chunks::RangeOrCellChunks;
+use crate::{Address, ChunkSize, Result, A1};
+use std::iter;
+
+/// A thin wrapper around `RangeOrCellChunks` which also reflects the `sheet_name` of the `A1`.
+pub struct A1Chunks {
+    sheet_name: Option<String>,
+    chunks: RangeOrCellChunks,
+}
+
+impl A1 {
+    /// Split the reference into chunks no larger than `size`, each with the same `sheet_name`.
+    /// See `RangeOrCell::chunks`.
+    pub fn chunks(&self, size: ChunkSize) -> Result<A1Chunks> {
+        Ok(A1Chunks {
+            sheet_name: self.sheet_name.clone(),
+            chunks: self.reference.chunks(size)?,
+        })
+    }
+
+    /// The reference one row at a time, each with the same `sheet_name`.  See
+    /// `RangeOrCell::rows`.
+    pub fn rows(&self) -> A1Chunks {
+        A1Chunks {
+            sheet_name: self.sheet_name.clone(),
+            chunks: self.reference.rows(),
+        }
+    }
+
+    /// The reference one row at a time, clipped to a sheet whose bottom-right cell is `bounds`.
+    /// See `RangeOrCell::rows_within`.
+    pub fn rows_within(&self, bounds: &Address) -> A1Chunks {
+        A1Chunks {
+            sheet_name: self.sheet_name.clone(),
+            chunks: self.reference.rows_within(bounds),
+        }
+    }
+
+    /// The reference one column at a time, each with the same `sheet_name`.  See
+    /// `RangeOrCell::columns`.
+    pub fn columns(&self) -> A1Chunks {
+        A1Chunks {
+            sheet_name: self.sheet_name.clone(),
+            chunks: self.reference.columns(),
+        }
+    }
+
+    /// The reference one column at a time, clipped to a sheet whose bottom-right cell is
+    /// `bounds`.  See `RangeOrCell::columns_within`.
+    pub fn columns_within(&self, bounds: &Address) -> A1Chunks {
+        A1Chunks {
+            sheet_name: self.sheet_name.clone(),
+            chunks: self.reference.columns_within(bounds),
+        }
+    }
+}
+
+impl iter::Iterator for A1Chunks {
+    type Item = A1;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(A1 {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.chunks.next()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn chunks() {
+        assert_eq!(
+            new("Foo!A1:B4")
+                .unwrap()
+                .chunks(ChunkSize::Rows(3))
+                .unwrap()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["Foo!A1:B3", "Foo!A4:B4"]
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let a1 = new("Foo!A2:F3").unwrap();
+
+        assert_eq!(
+            a1.rows().map(|a| a.to_string()).collect::<Vec<_>>(),
+            vec!["Foo!A2:F2", "Foo!A3:F3"]
+        );
+        assert_eq!(
+            a1.columns()
+                .take(2)
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["Foo!A2:A3", "Foo!B2:B3"]
+        );
+        assert_eq!(
+            new("Foo!B:B")
+                .unwrap()
+                .rows_within(&Address::new(5, 1))
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["Foo!B1", "Foo!B2"]
+        );
+        assert_eq!(
+            new("Foo!2:2")
+                .unwrap()
+                .columns_within(&Address::new(1, 5))
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["Foo!A2", "Foo!B2"]
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{Address, Result, A1};
+
+impl A1 {
+    /// The reference as it would be written in the cell `to` after being copied there from the
+    /// cell `from`, keeping the `sheet_name`.  See `RangeOrCell::copy_to`.
+    pub fn copy_to(&self, from: &Address, to: &Address) -> Result<Self> {
+        Ok(Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.copy_to(from, to)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn copy_to() {
+        let a1 = new("Foo!A$1:$B2").unwrap();
+
+        assert_eq!(
+            a1.copy_to(&(0, 0).into(), &(2, 2).into())
+                .unwrap()
+                .to_string(),
+            "Foo!C$1:$B4"
+        );
+        assert!(a1.copy_to(&(1, 0).into(), &(0, 0).into()).is_err());
+    }
+}
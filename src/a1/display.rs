@@ -1,28 +1,12 @@
+use crate::sheet_name;
 use crate::A1;
 use std::fmt;
 
-fn escape_quotes(sheet_name: &str) -> String {
-    sheet_name.replace('\'', "\\'")
-}
-
-fn needs_quotes(sheet_name: &str) -> bool {
-    for c in sheet_name.chars() {
-        if c.is_whitespace() || c == '\'' {
-            return true;
-        }
-    }
-    false
-}
-
 impl fmt::Display for A1 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let r = &self.reference;
         if let Some(sheet_name) = &self.sheet_name {
-            if needs_quotes(sheet_name) {
-                write!(f, "'{}'!{r}", escape_quotes(sheet_name))
-            } else {
-                write!(f, "{sheet_name}!{r}")
-            }
+            write!(f, "{}!{r}", sheet_name::quote(sheet_name))
         } else {
             write!(f, "{r}")
         }
@@ -53,6 +37,17 @@ mod tests {
         assert_eq!("'Foo Bar'!B2", a1.to_string());
     }
 
+    #[test]
+    fn display_quoted_sheet_name_escape() {
+        let a1 = A1 {
+            sheet_name: Some("Foo's Bar".to_string()),
+            reference: RangeOrCell::Cell((1, 1).into()),
+        };
+
+        assert_eq!("'Foo''s Bar'!B2", a1.to_string());
+        assert_eq!(new("'Foo''s Bar'!B2").unwrap(), a1);
+    }
+
     #[test]
     fn display_without_sheet_name() {
         let a1 = A1 {
@@ -0,0 +1,71 @@
+use crate::{Address, Index, RangeOrCell, Result, A1};
+
+impl A1 {
+    /// The cell at (zero-based) `row` and `column` from the top-left of the reference, keeping
+    /// the `sheet_name`.  See `RangeOrCell::cell_at`.
+    pub fn cell_at(&self, row: Index, column: Index) -> Result<Self> {
+        let address = self.reference.cell_at(row, column)?;
+        Ok(self.with_reference(RangeOrCell::Cell(address)))
+    }
+
+    /// The `k`th (zero-based) cell of the reference, keeping the `sheet_name`.  See
+    /// `RangeOrCell::nth_cell`.
+    pub fn nth_cell(&self, k: Index) -> Result<Self> {
+        let address = self.reference.nth_cell(k)?;
+        Ok(self.with_reference(RangeOrCell::Cell(address)))
+    }
+
+    /// How many cells into the reference `address` is.  See `RangeOrCell::position_of`.
+    pub fn position_of(&self, address: &Address) -> Option<Index> {
+        self.reference.position_of(address)
+    }
+
+    /// The (zero-based) `n`th row of the reference, keeping the `sheet_name`.  See
+    /// `RangeOrCell::nth_row`.
+    pub fn nth_row(&self, n: Index) -> Result<Self> {
+        Ok(self.with_reference(self.reference.nth_row(n)?))
+    }
+
+    /// The (zero-based) `n`th column of the reference, keeping the `sheet_name`.  See
+    /// `RangeOrCell::nth_column`.
+    pub fn nth_column(&self, n: Index) -> Result<Self> {
+        Ok(self.with_reference(self.reference.nth_column(n)?))
+    }
+
+    /// The (zero-based) `n`th area of the reference, keeping the `sheet_name`.  See
+    /// `RangeOrCell::area`.
+    pub fn area(&self, n: Index) -> Result<Self> {
+        Ok(self.with_reference(self.reference.area(n)?))
+    }
+
+    fn with_reference(&self, reference: RangeOrCell) -> Self {
+        Self {
+            sheet_name: self.sheet_name.clone(),
+            reference,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn cell_at() {
+        let a1 = new("Foo!B2:F9").unwrap();
+
+        assert_eq!(a1.cell_at(1, 2).unwrap().to_string(), "Foo!D3");
+        assert_eq!(a1.nth_cell(6).unwrap().to_string(), "Foo!C3");
+        assert_eq!(a1.position_of(&(2, 2).into()), Some(6));
+        assert!(a1.cell_at(8, 0).is_err());
+    }
+
+    #[test]
+    fn nth_row_and_column() {
+        let a1 = new("Foo!B2:F9,H1").unwrap();
+
+        assert_eq!(a1.nth_row(1).unwrap().to_string(), "Foo!B3:F3");
+        assert_eq!(a1.nth_column(4).unwrap().to_string(), "Foo!F2:F9");
+        assert_eq!(a1.area(1).unwrap().to_string(), "Foo!H1");
+    }
+}
@@ -11,8 +11,28 @@ impl Iterator for A1IntoIterator {
     fn next(&mut self) -> Option<Self::Item> {
         self.inner_iter.next()
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner_iter.nth(n)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner_iter.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for A1IntoIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner_iter.next_back()
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner_iter.nth_back(n)
+    }
 }
 
+impl ExactSizeIterator for A1IntoIterator {}
+
 impl IntoIterator for A1 {
     type Item = A1;
     type IntoIter = A1IntoIterator;
@@ -1,5 +1,5 @@
 use crate::range_or_cell::iterator::RangeOrCellIterator;
-use crate::A1;
+use crate::{RangeOrCell, Traversal, A1};
 use std::iter;
 
 pub struct A1Iterator {
@@ -9,9 +9,23 @@ pub struct A1Iterator {
 
 impl A1 {
     pub fn iter(&self) -> A1Iterator {
+        self.iter_with(Traversal::RowMajor)
+    }
+
+    /// Iterate in the given `traversal` order.  See `RangeOrCell::iter_with`.
+    pub fn iter_with(&self, traversal: Traversal) -> A1Iterator {
         A1Iterator {
             sheet_name: self.sheet_name.clone(),
-            reference_iter: self.reference.iter(),
+            reference_iter: self.reference.iter_with(traversal),
+        }
+    }
+}
+
+impl A1Iterator {
+    fn with_sheet_name(&self, reference: RangeOrCell) -> A1 {
+        A1 {
+            sheet_name: self.sheet_name.clone(),
+            reference,
         }
     }
 }
@@ -21,13 +35,44 @@ impl iter::Iterator for A1Iterator {
     type Item = A1;
 
     fn next(&mut self) -> Option<Self::Item> {
-        Some(A1 {
-            sheet_name: self.sheet_name.clone(),
-            reference: self.reference_iter.next()?.clone(),
-        })
+        let reference = self.reference_iter.next()?;
+        Some(self.with_sheet_name(reference))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let reference = self.reference_iter.nth(n)?;
+        Some(self.with_sheet_name(reference))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.reference_iter.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.reference_iter.count()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl iter::DoubleEndedIterator for A1Iterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let reference = self.reference_iter.next_back()?;
+        Some(self.with_sheet_name(reference))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let reference = self.reference_iter.nth_back(n)?;
+        Some(self.with_sheet_name(reference))
     }
 }
 
+impl iter::ExactSizeIterator for A1Iterator {}
+
+impl iter::FusedIterator for A1Iterator {}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -54,4 +99,26 @@ mod tests {
             vec!["Foo!A1", "Foo!A2", "Foo!A3", "Foo!A4", "Foo!A5"]
         );
     }
+
+    #[test]
+    fn iter_with_column_major() {
+        assert_eq!(
+            new("Foo!A1:B2")
+                .unwrap()
+                .iter_with(Traversal::ColumnMajor)
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["Foo!A1", "Foo!A2", "Foo!B1", "Foo!B2"]
+        );
+    }
+
+    #[test]
+    fn iter_rev_and_nth() {
+        let a1 = new("Foo!A1:C3").unwrap();
+
+        assert_eq!(a1.iter().len(), 9);
+        assert_eq!(a1.iter().nth(4).unwrap().to_string(), "Foo!B2");
+        assert_eq!(a1.iter().next_back().unwrap().to_string(), "Foo!C3");
+        assert_eq!(a1.iter().rev().nth(1).unwrap().to_string(), "Foo!B3");
+    }
 }
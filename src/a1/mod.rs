@@ -14,20 +14,37 @@
 //! * [Google Sheets API Overview](https://developers.google.com/sheets/api/guides/concepts)
 //! * [Refer to Cells and Ranges by Using A1 Notation](https://learn.microsoft.com/en-us/office/vba/excel/concepts/cells-and-ranges/refer-to-cells-and-ranges-by-using-a1-notation)
 //!
+use crate::sheet_name;
 use crate::RangeOrCell;
 use std::str;
 
+mod absolute;
+mod addresses;
+mod border;
+#[cfg(feature = "calamine")]
+mod calamine;
+mod chunks;
+mod copy;
 mod display;
 mod from_str;
+mod index;
 mod into_iterator;
 mod iterator;
-
+mod offset;
+#[cfg(feature = "rayon")]
+mod par_iter;
+mod sheets;
+mod structural;
+mod transpose;
+mod windows;
+
+/// `A1`s are ordered by `sheet_name` (with no sheet name first) and then by `reference`.
 #[cfg_attr(
     feature = "rkyv",
     derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)
 )]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct A1 {
     pub sheet_name: Option<String>,
     pub reference: RangeOrCell,
@@ -35,9 +52,25 @@ pub struct A1 {
 
 impl A1 {
     /// Is `other` completely contained within `self`?  They also must be in the same sheet
-    /// (meaning `self.sheet_name` == `other.sheet_name`).
+    /// (comparing sheet names case-insensitively, and no sheet name is only the same as no
+    /// sheet name).
     pub fn contains(&self, other: &Self) -> bool {
-        self.sheet_name == other.sheet_name && self.reference.contains(&other.reference)
+        self.is_same_sheet(other) && self.reference.contains(&other.reference)
+    }
+
+    /// Do `self` and `other` cover exactly the same cells of the same sheet?  See
+    /// `RangeOrCell::is_same_area` and `A1::contains`.
+    pub fn is_same_area(&self, other: &Self) -> bool {
+        self.is_same_sheet(other) && self.reference.is_same_area(&other.reference)
+    }
+
+    fn is_same_sheet(&self, other: &Self) -> bool {
+        sheet_name::is_same_sheet(self.sheet_name.as_deref(), other.sheet_name.as_deref())
+    }
+
+    /// Are `self` and `other` written exactly the same way?  This is the same as `==`.
+    pub fn is_identical(&self, other: &Self) -> bool {
+        self == other
     }
 
     /// Returns a new `A1` shifted downwards by `rows` rows.
@@ -124,6 +157,14 @@ mod tests {
         assert!(!a1_a.contains(&a1_b));
     }
 
+    #[test]
+    fn contains_sheet_name_case_insensitive() {
+        assert!(new("Data!A1:B2")
+            .unwrap()
+            .contains(&new("data!A1").unwrap()));
+        assert!(!new("Data!A1:B2").unwrap().contains(&new("A1").unwrap()));
+    }
+
     #[test]
     fn contains_true() {
         let a1_a = A1 {
@@ -138,6 +179,50 @@ mod tests {
         assert!(a1_a.contains(&a1_b));
     }
 
+    #[test]
+    fn is_same_area() {
+        let a1 = new("Foo!A1:B2").unwrap();
+
+        assert!(a1.is_same_area(&new("Foo!$B$2:$A$1").unwrap()));
+        assert!(a1.is_same_area(&new("foo!A1:B2").unwrap()));
+        assert!(!a1.is_same_area(&new("Bar!A1:B2").unwrap()));
+        assert!(!a1.is_same_area(&new("A1:B2").unwrap()));
+    }
+
+    #[test]
+    fn is_identical() {
+        let a1 = new("Foo!A1:B2").unwrap();
+
+        assert!(a1.is_identical(&new("Foo!A1:B2").unwrap()));
+        assert!(!a1.is_identical(&new("Foo!$A$1:B2").unwrap()));
+    }
+
+    #[test]
+    fn ord() {
+        let mut a1s = [
+            new("Foo!A1").unwrap(),
+            new("B2").unwrap(),
+            new("A1").unwrap(),
+            new("Bar!C3").unwrap(),
+        ];
+        a1s.sort();
+
+        assert_eq!(
+            a1s.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+            vec!["A1", "B2", "Bar!C3", "Foo!A1"]
+        );
+    }
+
+    #[test]
+    fn hash() {
+        let a1s = ["A1", "A1", "$A$1", "Foo!A1"]
+            .iter()
+            .map(|s| new(s).unwrap())
+            .collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(a1s.len(), 3);
+    }
+
     #[test]
     fn shift_down() {
         let a1 = A1 {
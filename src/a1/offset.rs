@@ -0,0 +1,82 @@
+use crate::{Address, Direction, Index, Result, A1};
+
+impl A1 {
+    /// Move the reference by `rows` and `columns`, keeping the `sheet_name`.  See
+    /// `RangeOrCell::offset`.
+    pub fn offset(&self, rows: isize, columns: isize) -> Result<Self> {
+        Ok(Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.offset(rows, columns)?,
+        })
+    }
+
+    /// Change the size of the reference while keeping its top-left corner.  See
+    /// `RangeOrCell::resize`.
+    pub fn resize(&self, height: Option<Index>, width: Option<Index>) -> Result<Self> {
+        Ok(Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.resize(height, width)?,
+        })
+    }
+
+    /// Grow the reference so that it also covers `address`.  See `RangeOrCell::expand_to`.
+    pub fn expand_to(&self, address: Address) -> Self {
+        Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.expand_to(address),
+        }
+    }
+
+    /// Grow the reference by `n` cells in the given `direction`.  See `RangeOrCell::extend`.
+    pub fn extend(&self, direction: Direction, n: Index) -> Result<Self> {
+        Ok(Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.extend(direction, n)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn offset() {
+        let a1 = new("Foo!B2:C3").unwrap();
+
+        assert_eq!(a1.offset(1, 2).unwrap().to_string(), "Foo!D3:E4");
+        assert!(a1.offset(0, -2).is_err());
+    }
+
+    #[test]
+    fn resize() {
+        assert_eq!(
+            new("Foo!B2")
+                .unwrap()
+                .resize(Some(2), Some(2))
+                .unwrap()
+                .to_string(),
+            "Foo!B2:C3"
+        );
+    }
+
+    #[test]
+    fn expand_to() {
+        assert_eq!(
+            new("Foo!B2").unwrap().expand_to((0, 0).into()).to_string(),
+            "Foo!A1:B2"
+        );
+    }
+
+    #[test]
+    fn extend() {
+        assert_eq!(
+            new("Foo!A:A")
+                .unwrap()
+                .extend(Direction::Right, 2)
+                .unwrap()
+                .to_string(),
+            "Foo!A:C"
+        );
+    }
+}
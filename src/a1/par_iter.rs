@@ -0,0 +1,89 @@
+use crate::range_or_cell::par_iter::RangeOrCellParIter;
+use crate::A1;
+use rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+/// A thin wrapper around `RangeOrCellParIter` which also reflects the `sheet_name` of the `A1`.
+#[derive(Clone, Debug)]
+pub struct A1ParIter {
+    sheet_name: Option<String>,
+    reference_iter: RangeOrCellParIter,
+}
+
+impl A1 {
+    /// Iterate over the reference in parallel, each with the same `sheet_name`.  See
+    /// `RangeOrCell::par_iter`.
+    pub fn par_iter(&self) -> A1ParIter {
+        A1ParIter {
+            sheet_name: self.sheet_name.clone(),
+            reference_iter: self.reference.par_iter(),
+        }
+    }
+}
+
+impl A1ParIter {
+    fn with_sheet_names(self) -> impl IndexedParallelIterator<Item = A1> {
+        let sheet_name = self.sheet_name;
+        self.reference_iter.map(move |reference| A1 {
+            sheet_name: sheet_name.clone(),
+            reference,
+        })
+    }
+}
+
+impl ParallelIterator for A1ParIter {
+    type Item = A1;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.with_sheet_names().drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        self.reference_iter.opt_len()
+    }
+}
+
+impl IndexedParallelIterator for A1ParIter {
+    fn len(&self) -> usize {
+        self.reference_iter.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.with_sheet_names().drive(consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        self.with_sheet_names().with_producer(callback)
+    }
+}
+
+impl IntoParallelIterator for &A1 {
+    type Item = A1;
+    type Iter = A1ParIter;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rayon::prelude::*;
+
+    #[test]
+    fn par_iter() {
+        for a1 in ["Foo!A1:D100", "'Bar Baz'!A1:B2,C3", "A:C"] {
+            let a1 = new(a1).unwrap();
+
+            assert_eq!(
+                a1.par_iter().collect::<Vec<_>>(),
+                a1.iter().collect::<Vec<_>>(),
+                "{a1}"
+            );
+        }
+    }
+}
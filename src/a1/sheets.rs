@@ -0,0 +1,70 @@
+use crate::sheet_name;
+use crate::A1;
+
+impl A1 {
+    /// Point the reference at `to` if it's on the sheet named `from` (ignoring case, like a
+    /// spreadsheet does), otherwise leave it as-is.  Quoting is taken care of when it's
+    /// displayed, so `to` is just the plain name.
+    pub fn rename_sheet(&self, from: &str, to: &str) -> Self {
+        match &self.sheet_name {
+            Some(sheet_name) if sheet_name::is_same(sheet_name, from) => Self {
+                sheet_name: Some(to.to_string()),
+                reference: self.reference.clone(),
+            },
+            _ => self.clone(),
+        }
+    }
+
+    /// Returns `None` (a `#REF!`) if the reference is on the sheet named `sheet_name`, which is
+    /// being deleted.  A reference without a sheet name is never affected.
+    pub fn delete_sheet(&self, sheet_name: &str) -> Option<Self> {
+        if matches!(&self.sheet_name, Some(s) if sheet_name::is_same(s, sheet_name)) {
+            None
+        } else {
+            Some(self.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn rename_sheet() {
+        let a1 = new("Q1!A1:B2").unwrap();
+
+        assert_eq!(
+            a1.rename_sheet("Q1", "Q1 2024").to_string(),
+            "'Q1 2024'!A1:B2"
+        );
+        assert_eq!(a1.rename_sheet("Q2", "Q2 2024"), a1);
+        assert_eq!(a1.rename_sheet("q1", "Q2").to_string(), "'Q2'!A1:B2");
+        assert_eq!(
+            new("A1").unwrap().rename_sheet("Q1", "Q2").to_string(),
+            "A1"
+        );
+    }
+
+    #[test]
+    fn rename_sheet_round_trips() {
+        let renamed = new("Q1!A1").unwrap().rename_sheet("Q1", "Bob's 'Q1'");
+
+        assert_eq!(renamed.to_string(), "'Bob''s ''Q1'''!A1");
+        assert_eq!(new(&renamed.to_string()).unwrap(), renamed);
+    }
+
+    #[test]
+    fn delete_sheet() {
+        assert_eq!(new("Q1!A1").unwrap().delete_sheet("Q1"), None);
+        assert_eq!(new("q1!A1").unwrap().delete_sheet("Q1"), None);
+        assert_eq!(
+            new("Q2!A1").unwrap().delete_sheet("Q1"),
+            Some(new("Q2!A1").unwrap())
+        );
+        assert_eq!(
+            new("A1").unwrap().delete_sheet("Q1"),
+            Some(new("A1").unwrap())
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::sheet_name;
+use crate::{Index, RangeOrCell, A1};
+
+impl A1 {
+    /// Adjust the reference for `n` rows being inserted before row `at` on the sheet named
+    /// `sheet_name`.  References to other sheets are returned as-is, and a reference without a
+    /// sheet name is only on the `None` sheet.  See `RangeOrCell::insert_rows`.
+    pub fn insert_rows(&self, sheet_name: Option<&str>, at: Index, n: Index) -> Option<Self> {
+        self.structural_edit(sheet_name, |r| r.insert_rows(at, n))
+    }
+
+    /// Adjust the reference for `n` rows being deleted starting at row `at` on the sheet named
+    /// `sheet_name`.  See `A1::insert_rows` and `RangeOrCell::delete_rows`.
+    pub fn delete_rows(&self, sheet_name: Option<&str>, at: Index, n: Index) -> Option<Self> {
+        self.structural_edit(sheet_name, |r| r.delete_rows(at, n))
+    }
+
+    /// Adjust the reference for `n` columns being inserted before column `at` on the sheet named
+    /// `sheet_name`.  See `A1::insert_rows` and `RangeOrCell::insert_columns`.
+    pub fn insert_columns(&self, sheet_name: Option<&str>, at: Index, n: Index) -> Option<Self> {
+        self.structural_edit(sheet_name, |r| r.insert_columns(at, n))
+    }
+
+    /// Adjust the reference for `n` columns being deleted starting at column `at` on the sheet
+    /// named `sheet_name`.  See `A1::insert_rows` and `RangeOrCell::delete_columns`.
+    pub fn delete_columns(&self, sheet_name: Option<&str>, at: Index, n: Index) -> Option<Self> {
+        self.structural_edit(sheet_name, |r| r.delete_columns(at, n))
+    }
+
+    fn structural_edit<F>(&self, sheet_name: Option<&str>, edit: F) -> Option<Self>
+    where
+        F: FnOnce(&RangeOrCell) -> Option<RangeOrCell>,
+    {
+        if !sheet_name::is_same_sheet(self.sheet_name.as_deref(), sheet_name) {
+            return Some(self.clone());
+        }
+
+        Some(Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: edit(&self.reference)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn insert_rows() {
+        let a1 = new("Foo!A5:B20").unwrap();
+
+        assert_eq!(
+            a1.insert_rows(Some("Foo"), 9, 3).unwrap().to_string(),
+            "Foo!A5:B23"
+        );
+        assert_eq!(a1.insert_rows(Some("Bar"), 9, 3), Some(a1.clone()));
+        assert_eq!(a1.insert_rows(None, 9, 3), Some(a1));
+    }
+
+    #[test]
+    fn insert_rows_sheet_name_case_insensitive() {
+        assert_eq!(
+            new("Data!A5")
+                .unwrap()
+                .insert_rows(Some("data"), 0, 1)
+                .unwrap()
+                .to_string(),
+            "Data!A6"
+        );
+    }
+
+    #[test]
+    fn delete_rows() {
+        let a1 = new("Foo!A5:B6").unwrap();
+
+        assert_eq!(a1.delete_rows(Some("Foo"), 4, 2), None);
+        assert_eq!(a1.delete_rows(Some("Bar"), 4, 2), Some(a1));
+    }
+
+    #[test]
+    fn insert_columns() {
+        assert_eq!(
+            new("C1")
+                .unwrap()
+                .insert_columns(None, 0, 2)
+                .unwrap()
+                .to_string(),
+            "E1"
+        );
+    }
+
+    #[test]
+    fn delete_columns() {
+        assert_eq!(
+            new("Foo!A:F")
+                .unwrap()
+                .delete_columns(Some("Foo"), 2, 3)
+                .unwrap()
+                .to_string(),
+            "Foo!A:C"
+        );
+    }
+}
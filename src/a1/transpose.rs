@@ -0,0 +1,45 @@
+use crate::{Address, Result, A1};
+
+impl A1 {
+    /// Flip the reference across the diagonal, keeping the `sheet_name`.  See
+    /// `RangeOrCell::transpose`.
+    pub fn transpose(&self) -> Self {
+        Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.transpose(),
+        }
+    }
+
+    /// Transpose the reference around `anchor`, keeping the `sheet_name`.  See
+    /// `RangeOrCell::transpose_around`.
+    pub fn transpose_around(&self, anchor: &Address) -> Result<Self> {
+        Ok(Self {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.reference.transpose_around(anchor)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn transpose() {
+        assert_eq!(
+            new("Foo!A1:$C2").unwrap().transpose().to_string(),
+            "Foo!A1:B$3"
+        );
+    }
+
+    #[test]
+    fn transpose_around() {
+        let a1 = new("Foo!B2:B4").unwrap();
+
+        assert_eq!(
+            a1.transpose_around(&(1, 1).into()).unwrap().to_string(),
+            "Foo!B2:D2"
+        );
+        assert!(a1.transpose_around(&(3, 0).into()).is_err());
+    }
+}
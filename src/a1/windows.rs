@@ -0,0 +1,85 @@
+use crate::range_or_cell::windows::RangeOrCellWindows;
+use crate::{Index, Result, A1};
+use std::iter;
+
+/// A thin wrapper around `RangeOrCellWindows` which also reflects the `sheet_name` of the `A1`.
+#[derive(Clone, Debug)]
+pub struct A1Windows {
+    sheet_name: Option<String>,
+    windows: RangeOrCellWindows,
+}
+
+impl A1 {
+    /// Every `height` by `width` window of the reference, each with the same `sheet_name`.  See
+    /// `RangeOrCell::windows`.
+    pub fn windows(&self, height: Index, width: Index) -> Result<A1Windows> {
+        Ok(A1Windows {
+            sheet_name: self.sheet_name.clone(),
+            windows: self.reference.windows(height, width)?,
+        })
+    }
+
+    /// Like `windows` but sliding `row_stride` rows and `column_stride` columns at a time.  See
+    /// `RangeOrCell::windows_with_stride`.
+    pub fn windows_with_stride(
+        &self,
+        height: Index,
+        width: Index,
+        row_stride: Index,
+        column_stride: Index,
+    ) -> Result<A1Windows> {
+        Ok(A1Windows {
+            sheet_name: self.sheet_name.clone(),
+            windows: self.reference.windows_with_stride(
+                height,
+                width,
+                row_stride,
+                column_stride,
+            )?,
+        })
+    }
+}
+
+impl iter::Iterator for A1Windows {
+    type Item = A1;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(A1 {
+            sheet_name: self.sheet_name.clone(),
+            reference: self.windows.next()?,
+        })
+    }
+}
+
+impl iter::FusedIterator for A1Windows {}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn windows() {
+        assert_eq!(
+            new("Foo!A1:A4")
+                .unwrap()
+                .windows(2, 1)
+                .unwrap()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["Foo!A1:A2", "Foo!A2:A3", "Foo!A3:A4"]
+        );
+    }
+
+    #[test]
+    fn windows_with_stride() {
+        assert_eq!(
+            new("Foo!A1:D1")
+                .unwrap()
+                .windows_with_stride(1, 2, 1, 2)
+                .unwrap()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["Foo!A1:B1", "Foo!C1:D1"]
+        );
+    }
+}
@@ -4,25 +4,52 @@
 //! `AsRef` or `Into` implementations.
 //!
 use crate::{Column, Index, Row};
+use std::cmp;
 
 mod as_ref;
 mod display;
 mod from;
 mod from_str;
 mod into;
+pub mod neighbors;
+mod ord;
+mod partial_ord;
 
 #[cfg_attr(
     feature = "rkyv",
     derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)
 )]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Address {
     pub column: Column,
     pub row: Row,
 }
 
 impl Address {
+    /// The next step of the F4 cycle a spreadsheet uses for toggling absolute references:
+    /// `A1` -> `$A$1` -> `A$1` -> `$A1` -> `A1`.
+    pub fn cycle_absolute(&self) -> Self {
+        let (column, row) = match (self.column.absolute, self.row.absolute) {
+            (false, false) => (true, true),
+            (true, true) => (false, true),
+            (false, true) => (true, false),
+            (true, false) => (false, false),
+        };
+
+        self.with_absolute(Some(column), Some(row))
+    }
+
+    /// `$A$1`
+    pub fn make_absolute(&self) -> Self {
+        self.with_absolute(Some(true), Some(true))
+    }
+
+    /// `A1`
+    pub fn make_relative(&self) -> Self {
+        self.with_absolute(Some(false), Some(false))
+    }
+
     pub fn new(column_index: Index, row_index: Index) -> Self {
         Self {
             column: column_index.into(),
@@ -33,16 +60,16 @@ impl Address {
     /// Given that `a` and `b` form a finite range, is `self` within it? i.e. is_between `a` and
     /// `b`.
     pub fn is_between(&self, a: &Self, b: &Self) -> bool {
-        let a_dist = a.origin_distance();
-        let b_dist = b.origin_distance();
-
-        let top_left = if a_dist < b_dist { a } else { b };
-        let bottom_right = if a_dist < b_dist { b } else { a };
+        self.column.is_between(a, b) && self.row.is_between(a, b)
+    }
 
-        self.column >= top_left.column
-            && self.row >= top_left.row
-            && self.column <= bottom_right.column
-            && self.row <= bottom_right.row
+    /// Move the address by a signed number of `rows` and `columns`.  Returns `None` if that would
+    /// move it off of the grid.  Unlike `with_x`/`with_y` this keeps the `absolute` flags.
+    pub fn offset(&self, rows: isize, columns: isize) -> Option<Self> {
+        Some(Self {
+            column: self.column.offset(columns)?,
+            row: self.row.offset(rows)?,
+        })
     }
 
     pub fn shift_down(&self, rows: Index) -> Self {
@@ -73,6 +100,32 @@ impl Address {
         }
     }
 
+    /// Swap the column and row (including their `absolute` flags), as if the grid were
+    /// transposed around `A1`.  So `C2` becomes `B3` and `$C2` becomes `B$3`.
+    pub fn transpose(&self) -> Self {
+        Self {
+            column: self.row.transpose(),
+            row: self.column.transpose(),
+        }
+    }
+
+    /// Transpose the address around `anchor`, which stays where it is.  Returns `None` if the
+    /// result would be off of the grid.
+    pub fn transpose_around(&self, anchor: &Self) -> Option<Self> {
+        Some(Self {
+            column: self.row.transpose_around(anchor)?,
+            row: self.column.transpose_around(anchor)?,
+        })
+    }
+
+    /// Set whether the column and row are `absolute`.  A `None` leaves that one as it is.
+    pub fn with_absolute(&self, column: Option<bool>, row: Option<bool>) -> Self {
+        Self {
+            column: column.map_or(self.column, |a| self.column.with_absolute(a)),
+            row: row.map_or(self.row, |a| self.row.with_absolute(a)),
+        }
+    }
+
     /// Set the `x` component with the following (hopefully sensical rules):
     pub fn with_x(&self, x: Index) -> Self {
         Self {
@@ -89,8 +142,30 @@ impl Address {
         }
     }
 
-    pub(crate) fn origin_distance(&self) -> usize {
-        self.column.x + self.row.y
+    /// The top-left and bottom-right corners of the rectangle formed by `a` and `b`.  Each
+    /// component keeps the `absolute` flag it had in `a` or `b`.
+    pub(crate) fn corners(a: &Self, b: &Self) -> (Self, Self) {
+        (
+            Self {
+                column: cmp::min(a.column, b.column),
+                row: cmp::min(a.row, b.row),
+            },
+            Self {
+                column: cmp::max(a.column, b.column),
+                row: cmp::max(a.row, b.row),
+            },
+        )
+    }
+
+    /// How many `(rows, columns)` it is from `self` to `to`, or `None` if that doesn't fit in an
+    /// `isize`.
+    pub(crate) fn delta_to(&self, to: &Self) -> Option<(isize, isize)> {
+        let signed = |i| isize::try_from(i).ok();
+
+        Some((
+            signed(to.row.y)?.checked_sub(signed(self.row.y)?)?,
+            signed(to.column.x)?.checked_sub(signed(self.column.x)?)?,
+        ))
     }
 }
 
@@ -98,12 +173,32 @@ impl Address {
 mod tests {
     use super::*;
 
+    #[test]
+    fn cycle_absolute() {
+        let cycle = std::iter::successors(Some(Address::new(0, 0)), |a| Some(a.cycle_absolute()))
+            .take(5)
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(cycle, vec!["A1", "$A$1", "A$1", "$A1", "A1"]);
+    }
+
+    #[test]
+    fn make_absolute_and_relative() {
+        let a = Address::new(0, 0).with_absolute(Some(true), None);
+
+        assert_eq!(a.to_string(), "$A1");
+        assert_eq!(a.make_absolute().to_string(), "$A$1");
+        assert_eq!(a.make_relative().to_string(), "A1");
+    }
+
     #[test]
     fn is_between_true() {
         let a: Address = (5, 5).into();
 
         assert!(a.is_between(&(0, 0).into(), &(10, 10).into()));
         assert!(a.is_between(&(4, 5).into(), &(6, 5).into()));
+        assert!(a.is_between(&(10, 0).into(), &(0, 10).into()));
     }
 
     #[test]
@@ -113,6 +208,35 @@ mod tests {
         assert!(!a.is_between(&(8, 8).into(), &(10, 10).into()));
     }
 
+    #[test]
+    fn offset() {
+        assert_eq!(Address::new(2, 2).offset(1, -2), Some((0, 3).into()));
+        assert_eq!(Address::new(2, 2).offset(-3, 0), None);
+    }
+
+    #[test]
+    fn transpose() {
+        let a = Address {
+            column: Column {
+                absolute: true,
+                x: 2,
+            },
+            row: Row::new(1),
+        };
+        assert_eq!(a.transpose().to_string(), "B$3");
+    }
+
+    #[test]
+    fn transpose_around() {
+        let anchor = Address::new(1, 1);
+        assert_eq!(anchor.transpose_around(&anchor), Some(anchor));
+        assert_eq!(
+            Address::new(3, 2).transpose_around(&anchor),
+            Some((2, 3).into())
+        );
+        assert_eq!(Address::new(0, 5).transpose_around(&(3, 1).into()), None);
+    }
+
     #[test]
     fn shift_down() {
         assert_eq!(Address::new(2, 2).shift_down(1), (2, 3).into());
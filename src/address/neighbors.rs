@@ -0,0 +1,103 @@
+use crate::Address;
+use std::iter;
+
+/// Which cells count as being next to each other.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Connectivity {
+    /// Only the cells directly above, below, left and right (a von Neumann neighborhood)
+    Four,
+
+    /// The four direct neighbors plus the diagonals (a Moore neighborhood)
+    Eight,
+}
+
+/// `(rows, columns)` offsets, in row-major order
+static FOUR: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+static EIGHT: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Emits the neighbors of an `Address` in row-major order, skipping any that would be off of
+/// the grid.
+#[derive(Clone, Debug)]
+pub struct Neighbors {
+    center: Address,
+    offsets: &'static [(isize, isize)],
+    i: usize,
+}
+
+impl Address {
+    /// The cells surrounding `self`, left-to-right then top-to-bottom.  Cells above row 1 or
+    /// left of column A are skipped, so `A1` only has 2 (or 3) neighbors.
+    pub fn neighbors(&self, connectivity: Connectivity) -> Neighbors {
+        Neighbors {
+            center: *self,
+            offsets: match connectivity {
+                Connectivity::Four => &FOUR,
+                Connectivity::Eight => &EIGHT,
+            },
+            i: 0,
+        }
+    }
+}
+
+impl iter::Iterator for Neighbors {
+    type Item = Address;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((rows, columns)) = self.offsets.get(self.i) {
+            self.i += 1;
+
+            if let Some(a) = self.center.offset(*rows, *columns) {
+                return Some(a);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn neighbors_to_strs(a: Address, connectivity: Connectivity) -> Vec<String> {
+        a.neighbors(connectivity).map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn neighbors_four() {
+        assert_eq!(
+            neighbors_to_strs(Address::new(1, 1), Connectivity::Four),
+            vec!["B1", "A2", "C2", "B3"]
+        );
+    }
+
+    #[test]
+    fn neighbors_eight() {
+        assert_eq!(
+            neighbors_to_strs(Address::new(1, 1), Connectivity::Eight),
+            vec!["A1", "B1", "C1", "A2", "C2", "A3", "B3", "C3"]
+        );
+    }
+
+    #[test]
+    fn neighbors_clipped() {
+        assert_eq!(
+            neighbors_to_strs(Address::new(0, 0), Connectivity::Four),
+            vec!["B1", "A2"]
+        );
+        assert_eq!(
+            neighbors_to_strs(Address::new(0, 1), Connectivity::Eight),
+            vec!["A1", "B1", "B2", "A3", "B3"]
+        );
+    }
+}
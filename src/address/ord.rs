@@ -0,0 +1,33 @@
+use super::Address;
+use std::cmp;
+
+/// Addresses are ordered row-major - first by row, then by column - which is the order you'd
+/// read them in.
+impl Ord for Address {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.row
+            .cmp(&other.row)
+            .then_with(|| self.column.cmp(&other.column))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cmp() {
+        assert_eq!(
+            Address::new(2, 2).cmp(&Address::new(2, 2)),
+            cmp::Ordering::Equal
+        );
+        assert_eq!(
+            Address::new(5, 1).cmp(&Address::new(0, 2)),
+            cmp::Ordering::Less
+        );
+        assert_eq!(
+            Address::new(1, 2).cmp(&Address::new(0, 2)),
+            cmp::Ordering::Greater
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use super::Address;
+use std::cmp;
+
+impl PartialOrd for Address {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn partial_cmp() {
+        assert_eq!(
+            Address::new(0, 5).partial_cmp(&Address::new(5, 0)),
+            Some(Ordering::Greater)
+        );
+    }
+}
@@ -1,5 +1,5 @@
 //! # Column
-use crate::Index;
+use crate::{Address, Index, Row};
 use std::cmp;
 
 mod as_ref;
@@ -8,7 +8,6 @@ mod from;
 mod from_str;
 mod into;
 mod ord;
-mod partial_eq;
 mod partial_ord;
 
 #[cfg_attr(
@@ -16,7 +15,7 @@ mod partial_ord;
     derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)
 )]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Copy, Clone, Debug, Eq)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Column {
     pub absolute: bool,
     pub x: Index,
@@ -25,12 +24,9 @@ pub struct Column {
 impl Column {
     /// Is `self` (inclusively) between the given `a` and `b` columns
     pub fn is_between<T: AsRef<Self>>(&self, a: T, b: T) -> bool {
-        let a_ref = a.as_ref();
-        let b_ref = b.as_ref();
-        let from = cmp::min(a_ref, b_ref);
-        let to = cmp::max(a_ref, b_ref);
+        let (a_x, b_x) = (a.as_ref().x, b.as_ref().x);
 
-        self >= from && self <= to
+        self.x >= cmp::min(a_x, b_x) && self.x <= cmp::max(a_x, b_x)
     }
 
     /// Does `self` contain the given column or address?  Either it's the same column or a point
@@ -39,10 +35,25 @@ impl Column {
         self.x == other.as_ref().x
     }
 
+    /// Toggle between `A` and `$A`.  A column on its own only has the one flag, so this is the
+    /// whole of F4's cycle.
+    pub fn cycle_absolute(&self) -> Self {
+        self.with_absolute(!self.absolute)
+    }
+
     pub fn new(x: Index) -> Self {
         Self { absolute: false, x }
     }
 
+    /// Move the column by a signed number of `columns` (negative is to the left).  Returns `None`
+    /// if that would move it to the left of column A.
+    pub fn offset(&self, columns: isize) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(columns)?,
+            ..*self
+        })
+    }
+
     /// Shift the column left by the given amount.
     pub fn shift_left(&self, columns: Index) -> Self {
         Self {
@@ -61,6 +72,32 @@ impl Column {
         }
     }
 
+    /// Flip the column into a row with the same index (and `absolute` flag), as if the grid were
+    /// transposed around `A1`.
+    pub fn transpose(&self) -> Row {
+        Row {
+            absolute: self.absolute,
+            y: self.x,
+        }
+    }
+
+    /// Flip the column into the row it lands on when the grid is transposed around `anchor`.
+    /// Returns `None` if that would be above row 1.
+    pub fn transpose_around(&self, anchor: &Address) -> Option<Row> {
+        Some(Row {
+            absolute: self.absolute,
+            y: self
+                .x
+                .checked_add(anchor.row.y)?
+                .checked_sub(anchor.column.x)?,
+        })
+    }
+
+    /// Set whether the column is `absolute` (`$A`) and return a `Copy`ed `Column`
+    pub fn with_absolute(&self, absolute: bool) -> Self {
+        Self { absolute, ..*self }
+    }
+
     /// Set the `x` and return a `Copy`ed `Column`
     pub fn with_x(&self, x: Index) -> Self {
         Self { x, ..*self }
@@ -71,6 +108,20 @@ impl Column {
 mod tests {
     use crate::*;
 
+    #[test]
+    fn cycle_absolute() {
+        let a = Column::new(0);
+
+        assert_eq!(a.cycle_absolute().to_string(), "$A");
+        assert_eq!(a.cycle_absolute().cycle_absolute(), a);
+    }
+
+    #[test]
+    fn with_absolute() {
+        assert_eq!(Column::new(1).with_absolute(true).to_string(), "$B");
+        assert_eq!(Column::new(1).with_absolute(false).to_string(), "B");
+    }
+
     #[test]
     fn contains_true() {
         assert!(Column::new(5).contains(Column::new(5)));
@@ -83,10 +134,29 @@ mod tests {
         assert!(!Column::new(5).contains(Address::new(50, 10)));
     }
 
+    #[test]
+    fn eq() {
+        assert_eq!(Column::new(0), Column::new(0));
+        assert_eq!(Column::new(100), Column::new(100));
+        assert_ne!(Column::new(1), Column::new(100));
+        assert_ne!(
+            Column::new(1),
+            Column {
+                absolute: true,
+                x: 1
+            }
+        );
+    }
+
     #[test]
     fn is_between_true() {
         assert!(Column::new(5).is_between(&Column::new(0), &Column::new(20)));
         assert!(Column::new(5).is_between(&Column::new(5), &Column::new(20)));
+        assert!(Column {
+            absolute: true,
+            x: 5
+        }
+        .is_between(&Column::new(0), &Column::new(5)));
     }
 
     #[test]
@@ -94,6 +164,33 @@ mod tests {
         assert!(!Column::new(5).is_between(&Column::new(0), &Column::new(2)));
     }
 
+    #[test]
+    fn offset() {
+        assert_eq!(Column::new(5).offset(3), Some(Column::new(8)));
+        assert_eq!(Column::new(5).offset(-5), Some(Column::new(0)));
+        assert_eq!(Column::new(5).offset(-6), None);
+    }
+
+    #[test]
+    fn transpose() {
+        let row = Column {
+            absolute: true,
+            x: 3,
+        }
+        .transpose();
+        assert_eq!(row.y, 3);
+        assert!(row.absolute);
+    }
+
+    #[test]
+    fn transpose_around() {
+        assert_eq!(
+            Column::new(3).transpose_around(&Address::new(2, 5)),
+            Some(Row::new(6))
+        );
+        assert_eq!(Column::new(0).transpose_around(&Address::new(2, 1)), None);
+    }
+
     #[test]
     fn shift_left() {
         assert_eq!(Column::new(5).shift_left(3), Column::new(2));
@@ -3,7 +3,10 @@ use std::cmp;
 
 impl Ord for Column {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
-        self.x.cmp(&other.x)
+        // `absolute` only breaks ties so that `Ord` agrees with `Eq`
+        self.x
+            .cmp(&other.x)
+            .then_with(|| self.absolute.cmp(&other.absolute))
     }
 }
 
@@ -19,5 +22,12 @@ mod tests {
             cmp::Ordering::Greater
         );
         assert_eq!(Column::new(20).cmp(&Column::new(21)), cmp::Ordering::Less);
+        assert_eq!(
+            Column::new(20).cmp(&Column {
+                absolute: true,
+                x: 20
+            }),
+            cmp::Ordering::Less
+        );
     }
 }
@@ -0,0 +1,20 @@
+//! # Direction
+//!
+//! One of the four directions you can move in on a grid.  Used by operations like
+//! `RangeOrCell::extend` which grow or move a reference along a single axis.
+
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    /// Towards row 1
+    Up,
+
+    /// Away from row 1
+    Down,
+
+    /// Towards column A
+    Left,
+
+    /// Away from column A
+    Right,
+}
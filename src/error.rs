@@ -1,15 +1,60 @@
 //! # Error
 use std::fmt;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Error {
     /// # A1ParseError
     ///
-    /// An error encountered by parsing a String into A1 format.
+    /// An error encountered by parsing a String into A1 format (or, with the `csv` feature, a
+    /// malformed CSV file).
     ///
-    /// * `bad_input` - The offending input that could not be parsed.
+    /// * `bad_input` - The offending input that could not be parsed (for a CSV file, which line
+    ///   it's on).
     /// * `message` - A relevant error message.
     A1ParseError { bad_input: String, message: String },
+
+    /// # OutOfBoundsError
+    ///
+    /// An operation would have produced a reference that is off of the grid (above row 1 or to
+    /// the left of column A) or that has no cells in it.
+    ///
+    /// * `reference` - The reference that the operation was applied to.
+    /// * `message` - A relevant error message.
+    OutOfBoundsError { reference: String, message: String },
+
+    /// # InvalidArgumentError
+    ///
+    /// An operation was given arguments that don't make sense for the reference it was applied to
+    /// (a window with no cells in it, for example).
+    ///
+    /// * `reference` - The reference that the operation was applied to.
+    /// * `message` - A relevant error message.
+    InvalidArgumentError { reference: String, message: String },
+
+    /// # MissingSheetError
+    ///
+    /// The sheet a reference is on isn't in the workbook (or the workbook doesn't have any sheets
+    /// at all).
+    ///
+    /// * `reference` - The reference that was being read.
+    /// * `message` - A relevant error message, including the name of the sheet.
+    MissingSheetError { reference: String, message: String },
+
+    /// # IoError
+    ///
+    /// Reading or writing a spreadsheet file (a CSV, for example) failed.
+    ///
+    /// * `message` - A relevant error message.
+    IoError { message: String },
+
+    /// # OverlapError
+    ///
+    /// Something was going to be written over part of the sheet that has already been written
+    /// to.
+    ///
+    /// * `reference` - The reference that would have been written to.
+    /// * `message` - A relevant error message.
+    OverlapError { reference: String, message: String },
 }
 
 impl Error {
@@ -19,6 +64,43 @@ impl Error {
             message: message.into(),
         }
     }
+
+    pub(crate) fn out_of_bounds<A: ToString, B: Into<String>>(reference: A, message: B) -> Self {
+        Self::OutOfBoundsError {
+            reference: reference.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn invalid_argument<A: ToString, B: Into<String>>(reference: A, message: B) -> Self {
+        Self::InvalidArgumentError {
+            reference: reference.to_string(),
+            message: message.into(),
+        }
+    }
+
+    #[cfg(feature = "calamine")]
+    pub(crate) fn missing_sheet<A: ToString, B: Into<String>>(reference: A, message: B) -> Self {
+        Self::MissingSheetError {
+            reference: reference.to_string(),
+            message: message.into(),
+        }
+    }
+
+    #[cfg(any(feature = "calamine", feature = "csv", feature = "rust_xlsxwriter"))]
+    pub(crate) fn io_error<A: ToString>(message: A) -> Self {
+        Self::IoError {
+            message: message.to_string(),
+        }
+    }
+
+    #[cfg(feature = "rust_xlsxwriter")]
+    pub(crate) fn overlap<A: ToString, B: Into<String>>(reference: A, message: B) -> Self {
+        Self::OverlapError {
+            reference: reference.to_string(),
+            message: message.into(),
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -27,6 +109,19 @@ impl fmt::Display for Error {
             Self::A1ParseError { message, bad_input } => {
                 write!(f, "{message} (input: `{bad_input}`)")
             }
+            Self::OutOfBoundsError { message, reference } => {
+                write!(f, "{message} (reference: `{reference}`)")
+            }
+            Self::InvalidArgumentError { message, reference } => {
+                write!(f, "{message} (reference: `{reference}`)")
+            }
+            Self::MissingSheetError { message, reference } => {
+                write!(f, "{message} (reference: `{reference}`)")
+            }
+            Self::IoError { message } => write!(f, "{message}"),
+            Self::OverlapError { message, reference } => {
+                write!(f, "{message} (reference: `{reference}`)")
+            }
         }
     }
 }
@@ -46,4 +141,63 @@ mod tests {
             "Foo was a bar (input: `bar`)"
         );
     }
+
+    #[test]
+    fn display_out_of_bounds_error() {
+        assert_eq!(
+            Error::OutOfBoundsError {
+                message: "Can't go there".to_string(),
+                reference: "A1".to_string(),
+            }
+            .to_string(),
+            "Can't go there (reference: `A1`)"
+        );
+    }
+
+    #[test]
+    fn display_invalid_argument_error() {
+        assert_eq!(
+            Error::InvalidArgumentError {
+                message: "Windows must be at least one cell in size".to_string(),
+                reference: "A1:B2".to_string(),
+            }
+            .to_string(),
+            "Windows must be at least one cell in size (reference: `A1:B2`)"
+        );
+    }
+
+    #[test]
+    fn display_missing_sheet_error() {
+        assert_eq!(
+            Error::MissingSheetError {
+                message: "The workbook doesn't have a sheet named `Foo`".to_string(),
+                reference: "Foo!A1".to_string(),
+            }
+            .to_string(),
+            "The workbook doesn't have a sheet named `Foo` (reference: `Foo!A1`)"
+        );
+    }
+
+    #[test]
+    fn display_io_error() {
+        assert_eq!(
+            Error::IoError {
+                message: "No such file".to_string(),
+            }
+            .to_string(),
+            "No such file"
+        );
+    }
+
+    #[test]
+    fn display_overlap_error() {
+        assert_eq!(
+            Error::OverlapError {
+                message: "It overlaps `A1:B2`".to_string(),
+                reference: "B2".to_string(),
+            }
+            .to_string(),
+            "It overlaps `A1:B2` (reference: `B2`)"
+        );
+    }
 }
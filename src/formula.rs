@@ -0,0 +1,420 @@
+//! # Sheet references in formulas
+//!
+//! Rewriting the sheet names in formula text when a sheet is renamed, deleted or moved.  Only
+//! the `Sheet!` prefixes are touched - everything else (including string literals and
+//! references into other workbooks like `[Book.xlsx]Sheet1!A1`) is left exactly as it was.
+//!
+//! Besides single sheets, a prefix can be a span of sheets (`Jan:Mar!B2`), which covers every
+//! sheet between the two in the workbook's order.  That's why deleting and reordering take the
+//! sheet order.
+//!
+//! ```
+//! use a1_notation::{delete_sheet_in_formula, rename_sheet_in_formula};
+//!
+//! assert_eq!(
+//!     rename_sheet_in_formula("=SUM(Q1!A1:A10)*'Q1'!B1", "Q1", "Q1 2024"),
+//!     "=SUM('Q1 2024'!A1:A10)*'Q1 2024'!B1"
+//! );
+//!
+//! let sheets = ["Jan", "Feb", "Mar"];
+//! assert_eq!(
+//!     delete_sheet_in_formula("=Jan!A1+SUM(Jan:Mar!B2)", &sheets, "Jan"),
+//!     "=#REF!+SUM(Feb:Mar!B2)"
+//! );
+//! ```
+use crate::sheet_name;
+
+/// The sheet (or sheets) before the `!` of a reference
+#[derive(Clone, Debug, PartialEq)]
+struct SheetSpan {
+    first: String,
+    last: Option<String>,
+}
+
+impl SheetSpan {
+    fn parse(names: &str) -> Option<Self> {
+        let mut names = names.split(':');
+        let span = Self {
+            first: names.next()?.to_string(),
+            last: names.next().map(|s| s.to_string()),
+        };
+
+        names.next().is_none().then_some(span)
+    }
+
+    fn to_prefix(&self) -> String {
+        match &self.last {
+            Some(last) => format!("{}!", sheet_name::quote_span(&self.first, last)),
+            None => format!("{}!", sheet_name::quote(&self.first)),
+        }
+    }
+}
+
+/// Rename the sheet `from` to `to` everywhere it's used in `formula`, including as either end of
+/// a span of sheets.  The new name will be quoted if it needs to be.
+pub fn rename_sheet_in_formula(formula: &str, from: &str, to: &str) -> String {
+    let rename = |name: &String| {
+        if sheet_name::is_same(name, from) {
+            to.to_string()
+        } else {
+            name.clone()
+        }
+    };
+
+    rewrite_sheet_prefixes(formula, |span| {
+        Some(SheetSpan {
+            first: rename(&span.first),
+            last: span.last.as_ref().map(rename),
+        })
+    })
+}
+
+/// Replace every reference to the sheet `sheet_name` in `formula` with `#REF!`, given that the
+/// workbook's sheets are in the order of `sheets` (before the delete).  Like a spreadsheet, a
+/// span of sheets which starts or ends on the deleted one shrinks to the next sheet in from that
+/// end, and only becomes a `#REF!` once there's nothing left of it.
+pub fn delete_sheet_in_formula<S: AsRef<str>>(
+    formula: &str,
+    sheets: &[S],
+    sheet_name: &str,
+) -> String {
+    rewrite_sheet_prefixes(formula, |span| {
+        let Some(last) = &span.last else {
+            return (!sheet_name::is_same(&span.first, sheet_name)).then(|| span.clone());
+        };
+        if !sheet_name::is_same(&span.first, sheet_name) && !sheet_name::is_same(last, sheet_name) {
+            return Some(span.clone());
+        }
+
+        let (first_i, last_i) = (position(sheets, &span.first)?, position(sheets, last)?);
+        if first_i == last_i {
+            return None;
+        }
+
+        // step the deleted end one sheet towards the other end
+        let toward = |i: usize, other: usize| if i < other { i + 1 } else { i - 1 };
+        let (first_i, last_i) = if sheet_name::is_same(&span.first, sheet_name) {
+            (toward(first_i, last_i), last_i)
+        } else {
+            (first_i, toward(last_i, first_i))
+        };
+
+        let name = |i: usize| sheets[i].as_ref().to_string();
+        Some(SheetSpan {
+            first: name(first_i),
+            last: (first_i != last_i).then(|| name(last_i)),
+        })
+    })
+}
+
+/// Fix up the spans of sheets in `formula` after the sheets were moved into the order of
+/// `sheets`.  A span still covers whatever is between its two ends, but if the ends were moved
+/// past each other they're swapped so that it reads left-to-right again.
+pub fn reorder_sheets_in_formula<S: AsRef<str>>(formula: &str, sheets: &[S]) -> String {
+    rewrite_sheet_prefixes(formula, |span| {
+        let Some(last) = &span.last else {
+            return Some(span.clone());
+        };
+
+        match (position(sheets, &span.first), position(sheets, last)) {
+            (Some(first_i), Some(last_i)) if first_i > last_i => Some(SheetSpan {
+                first: last.clone(),
+                last: Some(span.first.clone()),
+            }),
+            _ => Some(span.clone()),
+        }
+    })
+}
+
+fn position<S: AsRef<str>>(sheets: &[S], sheet_name: &str) -> Option<usize> {
+    sheets
+        .iter()
+        .position(|s| sheet_name::is_same(s.as_ref(), sheet_name))
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+fn is_reference_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '$' | '_' | '.')
+}
+
+/// The length of the `Sheet!` (or `'Sheet'!`) prefix at the start of `s`, if there is one
+fn sheet_prefix_len(s: &str) -> usize {
+    let len = if s.starts_with('\'') {
+        quoted(s, '\'').0
+    } else {
+        s.find(|c: char| !is_name_char(c)).unwrap_or(s.len())
+    };
+
+    if len > 0 && s[len..].starts_with('!') {
+        len + 1
+    } else {
+        0
+    }
+}
+
+/// The length of the reference at the start of `s` (which comes after its sheet prefix),
+/// including the other end of a range even if it has its own prefix like `A1:Sheet1!B2`
+fn reference_len(s: &str) -> usize {
+    let end = |s: &str| s.find(|c| !is_reference_char(c)).unwrap_or(s.len());
+
+    let len = end(s);
+    match s[len..].strip_prefix(':') {
+        Some(after) => {
+            let prefix = sheet_prefix_len(after);
+            len + 1 + prefix + end(&after[prefix..])
+        }
+        None => len,
+    }
+}
+
+/// The length of the `quote`-delimited token at the start of `s` (where a doubled quote is an
+/// escaped one) and its unescaped contents.  Unterminated tokens run to the end.
+fn quoted(s: &str, quote: char) -> (usize, String) {
+    let mut unquoted = String::new();
+    let mut chars = s.char_indices().skip(1).peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c == quote && chars.next_if(|(_, c)| *c == quote).is_none() {
+            return (i + c.len_utf8(), unquoted);
+        }
+        unquoted.push(c);
+    }
+
+    (s.len(), unquoted)
+}
+
+/// The length of the `[...]` at the start of `s`, allowing for nested brackets
+fn bracketed(s: &str) -> usize {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => (),
+        }
+    }
+    s.len()
+}
+
+/// Scan through `formula` calling `rewrite` with each sheet prefix.  If it returns a different
+/// span the prefix is re-written (and re-quoted) and if it returns `None` the whole reference is
+/// replaced by `#REF!`.
+fn rewrite_sheet_prefixes<F>(formula: &str, mut rewrite: F) -> String
+where
+    F: FnMut(&SheetSpan) -> Option<SheetSpan>,
+{
+    let mut out = String::with_capacity(formula.len());
+    let mut rest = formula;
+    let mut external = false;
+
+    while let Some(c) = rest.chars().next() {
+        let (len, names) = match c {
+            '"' => (quoted(rest, '"').0, None),
+            '\'' => {
+                let (len, unquoted) = quoted(rest, '\'');
+                (len, Some(unquoted))
+            }
+            '[' => {
+                let len = bracketed(rest);
+                out.push_str(&rest[..len]);
+                rest = &rest[len..];
+                // whatever sheet follows is in another workbook
+                external = true;
+                continue;
+            }
+            c if is_name_char(c) => {
+                let len = rest
+                    .find(|c: char| !(is_name_char(c) || c == ':'))
+                    .unwrap_or(rest.len());
+                let names = &rest[..len];
+
+                // an unquoted span has to be two plain sheet names - otherwise it's something
+                // like the range in `A1:Q1!B2`, and the prefix (if any) starts after the `:`
+                match names.rfind(':') {
+                    Some(i)
+                        if rest[len..].starts_with('!')
+                            && names.split(':').any(sheet_name::needs_quotes) =>
+                    {
+                        (i + 1, None)
+                    }
+                    _ => (len, Some(names.to_string())),
+                }
+            }
+            c => (c.len_utf8(), None),
+        };
+
+        let (token, after) = rest.split_at(len);
+        let span = names
+            .filter(|names| !external && !names.starts_with('['))
+            .filter(|_| after.starts_with('!'))
+            .and_then(|names| SheetSpan::parse(&names));
+        external = false;
+
+        let Some(span) = span else {
+            out.push_str(token);
+            rest = after;
+            continue;
+        };
+
+        let after = &after[1..];
+        match rewrite(&span) {
+            Some(rewritten) if rewritten == span => {
+                out.push_str(token);
+                out.push('!');
+                rest = after;
+            }
+            Some(rewritten) => {
+                out.push_str(&rewritten.to_prefix());
+                rest = after;
+            }
+            None => {
+                out.push_str("#REF!");
+                rest = &after[reference_len(after)..];
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename_sheet_in_formula_quoting() {
+        assert_eq!(
+            rename_sheet_in_formula("=Q1!A1+'Q1'!$B$2", "Q1", "Q1 2024"),
+            "='Q1 2024'!A1+'Q1 2024'!$B$2"
+        );
+        assert_eq!(
+            rename_sheet_in_formula("='Q1 2024'!A1", "Q1 2024", "Bob's"),
+            "='Bob''s'!A1"
+        );
+        assert_eq!(
+            rename_sheet_in_formula("='Bob''s'!A1", "Bob's", "Summary"),
+            "=Summary!A1"
+        );
+    }
+
+    #[test]
+    fn rename_sheet_in_formula_untouched() {
+        // a different sheet that happens to start the same way, a string, a function and a
+        // reference into another workbook
+        assert_eq!(
+            rename_sheet_in_formula(
+                r#"=Q10!A1&"Q1!A1"&Q1(A1)&[Book.xlsx]Q1!A1&'[Book.xlsx]Q1'!A1"#,
+                "Q1",
+                "Q2"
+            ),
+            r#"=Q10!A1&"Q1!A1"&Q1(A1)&[Book.xlsx]Q1!A1&'[Book.xlsx]Q1'!A1"#
+        );
+        // unnecessary quotes are kept as they were
+        assert_eq!(rename_sheet_in_formula("='Q2'!A1", "Q1", "Q3"), "='Q2'!A1");
+    }
+
+    #[test]
+    fn rename_sheet_in_formula_range_then_reference() {
+        // a range followed by a reference, not a span of sheets `A1` to `Q1` (which would have
+        // to be quoted)
+        assert_eq!(
+            rename_sheet_in_formula("=A1:Q1!B2", "Q1", "Q2"),
+            "=A1:'Q2'!B2"
+        );
+        assert_eq!(
+            rename_sheet_in_formula("=Jan:Q1!B2", "Q1", "Q2"),
+            "=Jan:'Q2'!B2"
+        );
+        assert_eq!(
+            rename_sheet_in_formula("='Jan:Q1'!B2", "Q1", "Q2"),
+            "='Jan:Q2'!B2"
+        );
+    }
+
+    #[test]
+    fn sheet_names_are_case_insensitive() {
+        assert_eq!(rename_sheet_in_formula("=q1!A1", "Q1", "Q2"), "='Q2'!A1");
+
+        let sheets = ["Jan", "Feb", "Mar"];
+        assert_eq!(
+            delete_sheet_in_formula("=FEB!A1+SUM(jan:mar!B2)", &sheets, "Feb"),
+            "=#REF!+SUM(jan:mar!B2)"
+        );
+        assert_eq!(
+            delete_sheet_in_formula("=SUM(jan:mar!B2)", &sheets, "JAN"),
+            "=SUM(Feb:Mar!B2)"
+        );
+    }
+
+    #[test]
+    fn rename_sheet_in_formula_span() {
+        assert_eq!(
+            rename_sheet_in_formula("=SUM(Jan:Mar!B2)", "Mar", "March 2024"),
+            "=SUM('Jan:March 2024'!B2)"
+        );
+    }
+
+    #[test]
+    fn delete_sheet_in_formula_single() {
+        let sheets = ["Jan", "Feb", "Mar"];
+
+        assert_eq!(
+            delete_sheet_in_formula("=Feb!A1:B2*2+Jan!C3", &sheets, "Feb"),
+            "=#REF!*2+Jan!C3"
+        );
+        assert_eq!(
+            delete_sheet_in_formula("='Feb'!$A$1", &sheets, "Feb"),
+            "=#REF!"
+        );
+        assert_eq!(
+            delete_sheet_in_formula("=Feb!A1:Feb!B2+1", &sheets, "Feb"),
+            "=#REF!+1"
+        );
+        assert_eq!(
+            delete_sheet_in_formula("=SUM('Feb'!A:B,Feb!3:4)", &sheets, "Feb"),
+            "=SUM(#REF!,#REF!)"
+        );
+    }
+
+    #[test]
+    fn delete_sheet_in_formula_span() {
+        let sheets = ["Jan", "Feb", "Mar", "Apr"];
+
+        assert_eq!(
+            delete_sheet_in_formula("=SUM(Jan:Mar!B2)", &sheets, "Mar"),
+            "=SUM(Jan:Feb!B2)"
+        );
+        assert_eq!(
+            delete_sheet_in_formula("=SUM(Feb:Mar!B2)", &sheets, "Feb"),
+            "=SUM(Mar!B2)"
+        );
+        assert_eq!(
+            delete_sheet_in_formula("=SUM(Feb:Mar!B2)", &sheets, "Apr"),
+            "=SUM(Feb:Mar!B2)"
+        );
+        assert_eq!(
+            delete_sheet_in_formula("=SUM(Feb:Feb!B2)", &sheets, "Feb"),
+            "=SUM(#REF!)"
+        );
+    }
+
+    #[test]
+    fn reorder_sheets_in_formula_span() {
+        assert_eq!(
+            reorder_sheets_in_formula("=SUM(Jan:Mar!B2)+Jan!A1", &["Mar", "Feb", "Jan"]),
+            "=SUM(Mar:Jan!B2)+Jan!A1"
+        );
+        assert_eq!(
+            reorder_sheets_in_formula("=SUM(Jan:Mar!B2)", &["Feb", "Jan", "Mar"]),
+            "=SUM(Jan:Mar!B2)"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Reading and writing a `Grid` as CSV (behind the `csv` feature).  The first record of the file
+//! is row 1 (or, if it has a header line, the first record after that) and the first field is
+//! column A, so values can be pulled out with `view` and written back with `paste`:
+//!
+//! ```
+//! use a1_notation::{Address, Grid, A1};
+//! use std::str::FromStr;
+//!
+//! let csv = "name,qty\nwidget,3\ngadget,5\n";
+//! let mut grid = Grid::from_csv(csv.as_bytes(), true).unwrap();
+//!
+//! let a1 = A1::from_str("A1:B2").unwrap();
+//! assert_eq!(
+//!     grid.view(&a1.reference).unwrap().to_rows(),
+//!     vec![vec!["widget", "3"], vec!["gadget", "5"]]
+//! );
+//!
+//! grid.paste(
+//!     Address::new(3, 0),
+//!     vec![vec!["total".to_string()], vec!["8".to_string()]],
+//! );
+//!
+//! let mut out = vec![];
+//! grid.write_csv(&mut out).unwrap();
+//! assert_eq!(
+//!     String::from_utf8(out).unwrap(),
+//!     "widget,3,,total\ngadget,5,,8\n"
+//! );
+//! ```
+//!
+//! A CSV file only has the one sheet, so `from_csv_range` reads an `A1` block out of it as long
+//! as the reference doesn't have a sheet name.
+use super::Grid;
+use crate::{Error, Result, A1};
+use csv::{ReaderBuilder, Writer};
+use std::{fs, io, path};
+
+impl Grid<String> {
+    /// Read CSV records into a grid.  If `has_headers` is set the first line is skipped, so row
+    /// 1 is the first line after it.  Records don't need to be the same length - the short ones
+    /// are padded with empty strings.
+    ///
+    /// Returns an `Error::IoError` if the reader fails and an `Error::A1ParseError` if the CSV
+    /// isn't valid (like a field that isn't UTF-8).
+    pub fn from_csv<R: io::Read>(reader: R, has_headers: bool) -> Result<Self> {
+        let mut reader = ReaderBuilder::new()
+            .has_headers(has_headers)
+            .flexible(true)
+            .from_reader(reader);
+
+        let rows = reader
+            .records()
+            .map(|record| Ok(record?.iter().map(String::from).collect()))
+            .collect::<std::result::Result<Vec<Vec<_>>, csv::Error>>()
+            .map_err(csv_error)?;
+
+        Ok(Self::from(rows))
+    }
+
+    /// Read just the block `a1` out of CSV records, with its top-left at `A1` of the grid.  See
+    /// `from_csv` and `Grid::view`.
+    ///
+    /// A CSV file doesn't have sheets, so a reference with a `sheet_name` is an
+    /// `Error::InvalidArgumentError`.  Otherwise it returns the same errors as `from_csv` and
+    /// `Grid::view`.
+    pub fn from_csv_range<R: io::Read>(reader: R, has_headers: bool, a1: &A1) -> Result<Self> {
+        if a1.sheet_name.is_some() {
+            return Err(Error::invalid_argument(
+                a1,
+                "A CSV file doesn't have any sheets to read from",
+            ));
+        }
+
+        let grid = Self::from_csv(reader, has_headers)?;
+        let rows = grid.view(&a1.reference)?.to_rows();
+
+        Ok(Self::from(rows))
+    }
+
+    /// Read a CSV file into a grid.  See `from_csv`.
+    pub fn from_csv_path<P: AsRef<path::Path>>(path: P, has_headers: bool) -> Result<Self> {
+        Self::from_csv(fs::File::open(path).map_err(Error::io_error)?, has_headers)
+    }
+}
+
+impl<T: AsRef<[u8]>> Grid<T> {
+    /// Write the grid out as CSV, one record per row.
+    pub fn write_csv<W: io::Write>(&self, writer: W) -> Result<()> {
+        let mut writer = Writer::from_writer(writer);
+        for row in self.rows() {
+            writer.write_record(row).map_err(csv_error)?;
+        }
+
+        writer.flush().map_err(Error::io_error)
+    }
+
+    /// Write the grid out to a CSV file, replacing it if it's already there.  See `write_csv`.
+    pub fn write_csv_path<P: AsRef<path::Path>>(&self, path: P) -> Result<()> {
+        self.write_csv(fs::File::create(path).map_err(Error::io_error)?)
+    }
+}
+
+/// Failing to read or write is an I/O error but anything else is a problem with the CSV itself
+fn csv_error(e: csv::Error) -> Error {
+    if let csv::ErrorKind::Io(_) = e.kind() {
+        return Error::io_error(e);
+    }
+
+    let line = e
+        .position()
+        .map_or_else(String::new, |p| format!("line {}", p.line()));
+    Error::parse_error(line, e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn r(s: &str) -> RangeOrCell {
+        RangeOrCell::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn from_csv() {
+        let grid = Grid::from_csv("a,b,c\n1,2\n3,4,5,6\n".as_bytes(), false).unwrap();
+
+        assert_eq!((grid.height(), grid.width()), (3, 4));
+        assert_eq!(grid["A1"], "a");
+        assert_eq!(grid["C2"], "");
+        assert_eq!(grid["D3"], "6");
+    }
+
+    #[test]
+    fn from_csv_with_headers() {
+        let grid = Grid::from_csv("x,y\n1,2\n3,4\n".as_bytes(), true).unwrap();
+
+        assert_eq!(grid["A1"], "1");
+        assert_eq!(
+            grid.view(&r("B:B")).unwrap().to_rows(),
+            vec![vec!["2"], vec!["4"]]
+        );
+    }
+
+    #[test]
+    fn from_csv_invalid() {
+        assert!(matches!(
+            Grid::from_csv(&b"a,\xff\n"[..], false),
+            Err(Error::A1ParseError { bad_input, .. }) if bad_input == "line 1"
+        ));
+        assert!(matches!(
+            Grid::from_csv_path("this/does/not/exist.csv", false),
+            Err(Error::IoError { .. })
+        ));
+    }
+
+    #[test]
+    fn from_csv_range() {
+        let csv = "a,b,c\n1,2,3\n4,5,6\n";
+
+        assert_eq!(
+            Grid::from_csv_range(csv.as_bytes(), true, &new("B1:C2").unwrap())
+                .unwrap()
+                .into_rows(),
+            vec![vec!["2", "3"], vec!["5", "6"]]
+        );
+        assert!(matches!(
+            Grid::from_csv_range(csv.as_bytes(), true, &new("Sheet1!B1:C2").unwrap()),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+        assert!(matches!(
+            Grid::from_csv_range(csv.as_bytes(), true, &new("B1:D2").unwrap()),
+            Err(Error::OutOfBoundsError { .. })
+        ));
+    }
+
+    #[test]
+    fn write_csv_pads() {
+        let mut grid = Grid::from_csv("a,b\n".as_bytes(), false).unwrap();
+        grid.paste(
+            Address::from_str("C3").unwrap(),
+            vec![vec!["x".to_string(), "y, z".to_string()]],
+        );
+
+        let mut out = vec![];
+        grid.write_csv(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a,b,,\n,,,\n,,x,\"y, z\"\n"
+        );
+    }
+
+    #[test]
+    fn csv_path_round_trip() {
+        let path = std::env::temp_dir().join(format!("a1_notation_{}.csv", std::process::id()));
+        let grid = Grid::from(vec![vec!["1", "2"], vec!["3", ""]]);
+
+        grid.write_csv_path(&path).unwrap();
+        let read = Grid::from_csv_path(&path, false);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            read.unwrap().into_rows(),
+            vec![vec!["1", "2"], vec!["3", ""]]
+        );
+    }
+}
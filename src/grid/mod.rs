@@ -0,0 +1,628 @@
+//! # Grid
+//!
+//! A dense, rectangular block of values starting at `A1` - for small sheets where every cell
+//! has something in it.  Cells can be looked up by `Address` or by A1 notation, and a `Range`
+//! (or a whole row or column) can be borrowed as a view without copying anything.
+//!
+//! ```
+//! use a1_notation::{Address, Grid, RangeOrCell};
+//! use std::str::FromStr;
+//!
+//! let mut grid = Grid::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+//! assert_eq!(grid["B2"], 5);
+//! assert_eq!(grid[Address::new(2, 0)], 3);
+//!
+//! // writing past the edge grows the grid
+//! grid.set(Address::new(3, 3), 10);
+//! assert_eq!((grid.height(), grid.width()), (4, 4));
+//!
+//! let view = grid.view(&RangeOrCell::from_str("B1:C2").unwrap()).unwrap();
+//! assert_eq!(view.rows().collect::<Vec<_>>(), vec![&[2, 3][..], &[5, 6][..]]);
+//! ```
+use crate::rect::Rect;
+use crate::sparse_grid::parse_cell;
+use crate::{Address, Error, Index, RangeOrCell, Result};
+use std::{iter, mem, ops};
+
+#[cfg(feature = "csv")]
+mod csv;
+
+/// A dense grid of values, stored row-by-row.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: Index,
+    height: Index,
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Grid<T> {
+    /// An empty grid, which grows as cells are `set`.
+    pub fn new() -> Self {
+        Self {
+            cells: vec![],
+            width: 0,
+            height: 0,
+        }
+    }
+
+    /// How many columns there are
+    pub fn width(&self) -> Index {
+        self.width
+    }
+
+    /// How many rows there are
+    pub fn height(&self) -> Index {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&T> {
+        let i = self.offset(address.column.x, address.row.y)?;
+        self.cells.get(i)
+    }
+
+    pub fn get_mut(&mut self, address: &Address) -> Option<&mut T> {
+        let i = self.offset(address.column.x, address.row.y)?;
+        self.cells.get_mut(i)
+    }
+
+    /// The (zero-based) row `y`
+    pub fn row(&self, y: Index) -> Option<&[T]> {
+        let start = self.offset(0, y)?;
+        Some(&self.cells[start..start + self.width])
+    }
+
+    /// The (zero-based) row `y`, mutably
+    pub fn row_mut(&mut self, y: Index) -> Option<&mut [T]> {
+        let start = self.offset(0, y)?;
+        Some(&mut self.cells[start..start + self.width])
+    }
+
+    /// The (zero-based) column `x`
+    pub fn column(&self, x: Index) -> Option<GridView<'_, T>> {
+        (x < self.width && self.height > 0).then(|| GridView {
+            grid: self,
+            rect: Rect {
+                left: x,
+                top: 0,
+                right: x,
+                bottom: self.height - 1,
+            },
+        })
+    }
+
+    /// Every row, top-to-bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` doesn't allow a width of zero, but then there aren't any cells anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every cell, row-by-row.
+    pub fn iter(&self) -> impl Iterator<Item = (Address, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (Address::new(i % width, i / width), value))
+    }
+
+    /// Borrow the cells of `range_or_cell`.  A `ColumnRange` or `RowRange` covers as many rows
+    /// or columns as the grid has.
+    ///
+    /// Returns an `Error::InvalidArgumentError` if the reference is a `NonContiguous` and an
+    /// `Error::OutOfBoundsError` if it goes outside of the grid.
+    pub fn view(&self, range_or_cell: &RangeOrCell) -> Result<GridView<'_, T>> {
+        Ok(GridView {
+            rect: self.view_rect(range_or_cell)?,
+            grid: self,
+        })
+    }
+
+    /// Mutably borrow the cells of `range_or_cell`.  See `view`.
+    pub fn view_mut(&mut self, range_or_cell: &RangeOrCell) -> Result<GridViewMut<'_, T>> {
+        Ok(GridViewMut {
+            rect: self.view_rect(range_or_cell)?,
+            width: self.width,
+            cells: &mut self.cells,
+        })
+    }
+
+    /// The rows of the grid as nested vectors.
+    pub fn into_rows(self) -> Vec<Vec<T>> {
+        let mut cells = self.cells.into_iter();
+        (0..self.height)
+            .map(|_| cells.by_ref().take(self.width).collect())
+            .collect()
+    }
+
+    /// Where the cell at `(x, y)` is in `cells`, if it's on the grid
+    fn offset(&self, x: Index, y: Index) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    fn view_rect(&self, range_or_cell: &RangeOrCell) -> Result<Rect> {
+        if let RangeOrCell::NonContiguous(_) = range_or_cell {
+            return Err(Error::invalid_argument(
+                range_or_cell,
+                "Only a contiguous reference can be viewed",
+            ));
+        }
+
+        let rect = match &range_or_cell.rects()[..] {
+            [rect] => {
+                // whole rows and columns only go as far as the grid does
+                let clip = |end: Index, size: Index| {
+                    if end == Index::MAX {
+                        size.checked_sub(1)
+                    } else {
+                        Some(end)
+                    }
+                };
+
+                clip(rect.right, self.width)
+                    .zip(clip(rect.bottom, self.height))
+                    .map(|(right, bottom)| Rect {
+                        right,
+                        bottom,
+                        ..*rect
+                    })
+                    .filter(|r| {
+                        r.left <= r.right
+                            && r.top <= r.bottom
+                            && r.right < self.width
+                            && r.bottom < self.height
+                    })
+            }
+            _ => None,
+        };
+
+        rect.ok_or_else(|| Error::out_of_bounds(range_or_cell, "It goes outside of the grid"))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `height` by `width` grid with every cell set to `value`.
+    ///
+    /// Panics if `height * width` overflows a `usize`.
+    pub fn filled(height: Index, width: Index, value: T) -> Self {
+        Self {
+            cells: vec![value; cell_count(height, width)],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T: Default> Grid<T> {
+    /// Set the cell at `address`, growing the grid to fit it if it's past the edge.  The new
+    /// cells are filled with `T::default()`.  Returns what was in the cell before.
+    ///
+    /// Panics if the grown grid would have more cells than fit in a `usize`.
+    pub fn set(&mut self, address: Address, value: T) -> T {
+        let (x, y) = (address.column.x, address.row.y);
+        self.grow(
+            self.height.max(y.saturating_add(1)),
+            self.width.max(x.saturating_add(1)),
+        );
+
+        mem::replace(&mut self.cells[y * self.width + x], value)
+    }
+
+    /// Grow the grid to at least `height` by `width`, filling the new cells with
+    /// `T::default()`.  The grid never shrinks.
+    ///
+    /// Panics if `height * width` overflows a `usize`.
+    pub fn grow(&mut self, height: Index, width: Index) {
+        let (height, width) = (height.max(self.height), width.max(self.width));
+        let len = cell_count(height, width);
+
+        if width > self.width {
+            let mut old = mem::take(&mut self.cells).into_iter();
+            let mut cells = Vec::with_capacity(len);
+            for _ in 0..self.height {
+                cells.extend(old.by_ref().take(self.width));
+                cells.extend(iter::repeat_with(T::default).take(width - self.width));
+            }
+            self.cells = cells;
+            self.width = width;
+        }
+
+        self.cells.resize_with(len, T::default);
+        self.height = height;
+    }
+
+    /// Write a block of rows with its top-left at `anchor`, growing the grid to fit it.  Rows
+    /// can be different lengths and only the cells they have are written.
+    pub fn paste<R, I>(&mut self, anchor: Address, rows: R)
+    where
+        R: IntoIterator<Item = I>,
+        I: IntoIterator<Item = T>,
+    {
+        let (left, top) = (anchor.column.x, anchor.row.y);
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, value) in row.into_iter().enumerate() {
+                self.set(Address::new(left + x, top + y), value);
+            }
+        }
+    }
+}
+
+/// How many cells a `height` by `width` grid has
+fn cell_count(height: Index, width: Index) -> Index {
+    height
+        .checked_mul(width)
+        .unwrap_or_else(|| panic!("A {height}x{width} grid has too many cells to fit in memory"))
+}
+
+/// Rows of different lengths are padded out with `T::default()`.
+impl<T: Default> From<Vec<Vec<T>>> for Grid<T> {
+    fn from(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut cells = Vec::with_capacity(height * width);
+        for row in rows {
+            let len = row.len();
+            cells.extend(row);
+            cells.extend(iter::repeat_with(T::default).take(width - len));
+        }
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> From<Grid<T>> for Vec<Vec<T>> {
+    fn from(grid: Grid<T>) -> Self {
+        grid.into_rows()
+    }
+}
+
+/// Panics if `address` is off of the grid.
+impl<T> ops::Index<Address> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, address: Address) -> &Self::Output {
+        self.get(&address)
+            .unwrap_or_else(|| panic!("{address} is outside of the grid"))
+    }
+}
+
+/// Panics if `address` is off of the grid.  Use `set` to grow the grid as needed.
+impl<T> ops::IndexMut<Address> for Grid<T> {
+    fn index_mut(&mut self, address: Address) -> &mut Self::Output {
+        self.get_mut(&address)
+            .unwrap_or_else(|| panic!("{address} is outside of the grid"))
+    }
+}
+
+/// Panics if `a1` isn't a single cell or is off of the grid.
+impl<T> ops::Index<&str> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, a1: &str) -> &Self::Output {
+        &self[parse_cell(a1).unwrap_or_else(|e| panic!("{e}"))]
+    }
+}
+
+/// Panics if `a1` isn't a single cell or is off of the grid.
+impl<T> ops::IndexMut<&str> for Grid<T> {
+    fn index_mut(&mut self, a1: &str) -> &mut Self::Output {
+        &mut self[parse_cell(a1).unwrap_or_else(|e| panic!("{e}"))]
+    }
+}
+
+/// A block of a `Grid`, as returned by `Grid::view`.  Positions within it are relative to its
+/// top-left.
+#[derive(Clone, Copy, Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    rect: Rect,
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> Index {
+        self.rect.right - self.rect.left + 1
+    }
+
+    pub fn height(&self) -> Index {
+        self.rect.bottom - self.rect.top + 1
+    }
+
+    /// The cell at (zero-based) `row` and `column` from the top-left of the view
+    pub fn get(&self, row: Index, column: Index) -> Option<&'a T> {
+        if row >= self.height() || column >= self.width() {
+            return None;
+        }
+
+        self.grid
+            .get(&Address::new(self.rect.left + column, self.rect.top + row))
+    }
+
+    /// Each row of the view, top-to-bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let (grid, rect) = (self.grid, self.rect);
+        (rect.top..=rect.bottom).map(move |y| {
+            let start = y * grid.width;
+            &grid.cells[start + rect.left..=start + rect.right]
+        })
+    }
+
+    /// Every cell of the view (with its `Address` on the grid), row-by-row.
+    pub fn iter(&self) -> impl Iterator<Item = (Address, &'a T)> {
+        let rect = self.rect;
+        self.rows().enumerate().flat_map(move |(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, value)| (Address::new(rect.left + x, rect.top + y), value))
+        })
+    }
+}
+
+/// A mutable block of a `Grid`, as returned by `Grid::view_mut`.  Positions within it are
+/// relative to its top-left.
+#[derive(Debug)]
+pub struct GridViewMut<'a, T> {
+    cells: &'a mut [T],
+    width: Index,
+    rect: Rect,
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copy the cells of the view out as nested vectors.
+    pub fn to_rows(&self) -> Vec<Vec<T>> {
+        self.rows().map(<[T]>::to_vec).collect()
+    }
+}
+
+impl<T> GridViewMut<'_, T> {
+    pub fn width(&self) -> Index {
+        self.rect.right - self.rect.left + 1
+    }
+
+    pub fn height(&self) -> Index {
+        self.rect.bottom - self.rect.top + 1
+    }
+
+    /// The cell at (zero-based) `row` and `column` from the top-left of the view
+    pub fn get(&self, row: Index, column: Index) -> Option<&T> {
+        let i = self.offset(row, column)?;
+        self.cells.get(i)
+    }
+
+    /// The cell at (zero-based) `row` and `column` from the top-left of the view, mutably
+    pub fn get_mut(&mut self, row: Index, column: Index) -> Option<&mut T> {
+        let i = self.offset(row, column)?;
+        self.cells.get_mut(i)
+    }
+
+    /// Each row of the view, top-to-bottom.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let rect = self.rect;
+        self.cells
+            .chunks_mut(self.width)
+            .skip(rect.top)
+            .take(rect.bottom - rect.top + 1)
+            .map(move |row| &mut row[rect.left..=rect.right])
+    }
+
+    fn offset(&self, row: Index, column: Index) -> Option<usize> {
+        (row < self.height() && column < self.width())
+            .then(|| (self.rect.top + row) * self.width + self.rect.left + column)
+    }
+}
+
+impl<T: Clone> GridViewMut<'_, T> {
+    /// Set every cell of the view to `value`.
+    pub fn fill(&mut self, value: T) {
+        for row in self.rows_mut() {
+            row.fill(value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn r(s: &str) -> RangeOrCell {
+        RangeOrCell::from_str(s).unwrap()
+    }
+
+    fn grid() -> Grid<usize> {
+        Grid::from(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]])
+    }
+
+    #[test]
+    fn index() {
+        let mut grid = grid();
+
+        assert_eq!(grid["A1"], 1);
+        assert_eq!(grid["$C$2"], 6);
+        assert_eq!(grid[Address::new(1, 2)], 8);
+
+        grid["B2"] = 50;
+        grid[Address::new(0, 0)] += 10;
+        assert_eq!(grid.row(1), Some(&[4, 50, 6][..]));
+        assert_eq!(grid.get(&Address::new(0, 0)), Some(&11));
+        assert_eq!(grid.get(&Address::new(3, 0)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "D1 is outside of the grid")]
+    fn index_off_grid() {
+        let _ = grid()["D1"];
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_not_a_cell() {
+        let _ = grid()["A1:B2"];
+    }
+
+    #[test]
+    #[should_panic(expected = "too many cells")]
+    fn filled_too_big() {
+        Grid::filled(Index::MAX, 2, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "too many cells")]
+    fn set_too_far() {
+        let mut grid = Grid::filled(2, 2, 0);
+        grid.set(Address::new(Index::MAX - 1, Index::MAX - 1), 1);
+    }
+
+    #[test]
+    fn set_grows() {
+        let mut grid = Grid::new();
+
+        assert_eq!(grid.set(Address::new(1, 0), "b"), "");
+        assert_eq!(grid.set(Address::new(0, 2), "c"), "");
+        assert_eq!(grid.set(Address::new(0, 2), "d"), "c");
+        assert_eq!(
+            grid.into_rows(),
+            vec![vec!["", "b"], vec!["", ""], vec!["d", ""]]
+        );
+    }
+
+    #[test]
+    fn paste() {
+        let mut grid = grid();
+        grid.paste(Address::new(2, 1), vec![vec![10, 11], vec![12]]);
+
+        assert_eq!(
+            grid.into_rows(),
+            vec![vec![1, 2, 3, 0], vec![4, 5, 10, 11], vec![7, 8, 12, 0]]
+        );
+    }
+
+    #[test]
+    fn from_ragged_rows() {
+        let grid = Grid::from(vec![vec![1], vec![2, 3]]);
+
+        assert_eq!((grid.height(), grid.width()), (2, 2));
+        assert_eq!(Vec::from(grid), vec![vec![1, 0], vec![2, 3]]);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.row(2), Some(&[7, 8, 9][..]));
+        assert_eq!(grid.row(3), None);
+        assert_eq!(
+            grid.column(1)
+                .unwrap()
+                .iter()
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            vec![2, 5, 8]
+        );
+        assert!(grid.column(3).is_none());
+        assert_eq!(Grid::<usize>::new().rows().count(), 0);
+    }
+
+    #[test]
+    fn iter() {
+        assert_eq!(
+            grid()
+                .iter()
+                .filter(|(_, v)| **v % 4 == 0)
+                .map(|(a, _)| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["A2", "B3"]
+        );
+    }
+
+    #[test]
+    fn view() {
+        let grid = grid();
+        let view = grid.view(&r("C3:B2")).unwrap();
+
+        assert_eq!((view.height(), view.width()), (2, 2));
+        assert_eq!(view.get(0, 1), Some(&6));
+        assert_eq!(view.get(2, 0), None);
+        assert_eq!(
+            view.rows().collect::<Vec<_>>(),
+            vec![&[5, 6][..], &[8, 9][..]]
+        );
+        assert_eq!(view.iter().next().unwrap().0.to_string(), "B2");
+        assert_eq!(view.to_rows(), vec![vec![5, 6], vec![8, 9]]);
+    }
+
+    #[test]
+    fn view_whole_rows_and_columns() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.view(&r("B:C")).unwrap().rows().collect::<Vec<_>>(),
+            vec![&[2, 3][..], &[5, 6][..], &[8, 9][..]]
+        );
+        assert_eq!(
+            grid.view(&r("2:2")).unwrap().rows().collect::<Vec<_>>(),
+            vec![&[4, 5, 6][..]]
+        );
+    }
+
+    #[test]
+    fn view_outside() {
+        let grid = grid();
+
+        assert!(matches!(
+            grid.view(&r("B2:D4")),
+            Err(Error::OutOfBoundsError { .. })
+        ));
+        assert!(matches!(
+            grid.view(&r("D:D")),
+            Err(Error::OutOfBoundsError { .. })
+        ));
+        assert!(matches!(
+            grid.view(&r("A1,B2")),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+        assert!(Grid::<usize>::new().view(&r("A1")).is_err());
+    }
+
+    #[test]
+    fn view_mut() {
+        let mut grid = grid();
+
+        let mut view = grid.view_mut(&r("B2:C3")).unwrap();
+        *view.get_mut(1, 1).unwrap() = 90;
+        assert_eq!(view.get(1, 1), Some(&90));
+        for row in view.rows_mut() {
+            row[0] = 0;
+        }
+        assert_eq!(
+            grid.into_rows(),
+            vec![vec![1, 2, 3], vec![4, 0, 6], vec![7, 0, 90]]
+        );
+
+        let mut other = self::grid();
+        other.view_mut(&r("A:A")).unwrap().fill(0);
+        assert_eq!(
+            other
+                .column(0)
+                .unwrap()
+                .iter()
+                .map(|(_, v)| *v)
+                .sum::<usize>(),
+            0
+        );
+    }
+}
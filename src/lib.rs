@@ -191,16 +191,38 @@ use std::str::FromStr;
 mod a1;
 mod address;
 mod column;
+mod direction;
 mod error;
+mod formula;
+mod grid;
+mod range_move;
 mod range_or_cell;
+mod rect;
 mod row;
+mod sheet_name;
+mod sparse_grid;
+mod spatial_index;
+mod traversal;
+#[cfg(feature = "rust_xlsxwriter")]
+mod xlsx_writer;
 
 pub use a1::A1;
+pub use address::neighbors::Connectivity;
 pub use address::Address;
 pub use column::Column;
+pub use direction::Direction;
 pub use error::Error;
+pub use formula::{delete_sheet_in_formula, rename_sheet_in_formula, reorder_sheets_in_formula};
+pub use grid::{Grid, GridView, GridViewMut};
+pub use range_move::RangeMove;
+pub use range_or_cell::chunks::ChunkSize;
 pub use range_or_cell::RangeOrCell;
 pub use row::Row;
+pub use sparse_grid::SparseGrid;
+pub use spatial_index::SpatialIndex;
+pub use traversal::Traversal;
+#[cfg(feature = "rust_xlsxwriter")]
+pub use xlsx_writer::XlsxWriter;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
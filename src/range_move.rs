@@ -0,0 +1,274 @@
+//! # RangeMove
+//!
+//! Cutting a block of cells and pasting it somewhere else, and rewriting references the way a
+//! spreadsheet engine does afterwards:
+//!
+//! * A reference that's entirely inside of the moved block moves along with it.
+//! * A reference that's entirely inside of the destination (and wasn't moved) now points at
+//!   cells that were overwritten, so it becomes a `#REF!`.
+//! * Anything else, including references that only partially overlap the moved block, stays
+//!   as-is.
+//!
+//! ```
+//! use a1_notation::{new, Address, RangeMove, RangeOrCell};
+//! use std::str::FromStr;
+//!
+//! let source = RangeOrCell::from_str("B2:D5").unwrap();
+//! let cut_paste = RangeMove::new(source, Address::new(7, 9), Some("Sheet1")).unwrap();
+//!
+//! // moved along with the block
+//! assert_eq!(
+//!     cut_paste.remap(&new("Sheet1!C3").unwrap()).unwrap().to_string(),
+//!     "Sheet1!I11"
+//! );
+//!
+//! // overwritten by the paste
+//! assert_eq!(cut_paste.remap(&new("Sheet1!H10").unwrap()), None);
+//!
+//! // only partially moved, so it's left alone
+//! assert_eq!(
+//!     cut_paste.remap(&new("Sheet1!A1:C3").unwrap()).unwrap().to_string(),
+//!     "Sheet1!A1:C3"
+//! );
+//! ```
+use crate::rect::Rect;
+use crate::sheet_name;
+use crate::{Address, Error, RangeOrCell, Result, A1};
+
+/// A block of cells cut from `source` and pasted with its top-left corner at a destination
+/// `Address`, on the sheet named `sheet_name`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RangeMove {
+    sheet_name: Option<String>,
+    source: RangeOrCell,
+    rows: isize,
+    columns: isize,
+    source_rect: Rect,
+    destination_rect: Rect,
+}
+
+impl RangeMove {
+    /// Move `source` so that its top-left corner ends up at `destination`.  Whole columns can
+    /// only be moved sideways (so `destination` has to be in row 1) and whole rows can only be
+    /// moved up or down (so `destination` has to be in column A).
+    ///
+    /// Returns an `Error::OutOfBoundsError` if `source` is `NonContiguous` (which you can't cut
+    /// and paste as one block) or if the destination would go off of the grid.
+    pub fn new(
+        source: RangeOrCell,
+        destination: Address,
+        sheet_name: Option<&str>,
+    ) -> Result<Self> {
+        let source_rect = match source.rects()[..] {
+            [rect] if !matches!(source, RangeOrCell::NonContiguous(_)) => rect,
+            _ => {
+                return Err(Error::out_of_bounds(
+                    &source,
+                    "Only a single contiguous block of cells can be moved",
+                ))
+            }
+        };
+
+        let (rows, columns) = Address::new(source_rect.left, source_rect.top)
+            .delta_to(&destination)
+            .ok_or_else(|| Error::out_of_bounds(&source, "Moving it there would leave the grid"))?;
+
+        let destination_rect = match source {
+            RangeOrCell::ColumnRange { .. } if rows != 0 => None,
+            RangeOrCell::RowRange { .. } if columns != 0 => None,
+            _ => source.offset(rows, columns).ok().map(|r| r.rects()[0]),
+        }
+        .ok_or_else(|| Error::out_of_bounds(&source, "Moving it there would leave the grid"))?;
+
+        Ok(Self {
+            sheet_name: sheet_name.map(|s| s.to_string()),
+            source,
+            rows,
+            columns,
+            source_rect,
+            destination_rect,
+        })
+    }
+
+    /// The sheet the move is on
+    pub fn sheet_name(&self) -> Option<&str> {
+        self.sheet_name.as_deref()
+    }
+
+    /// The block of cells that was cut
+    pub fn source(&self) -> &RangeOrCell {
+        &self.source
+    }
+
+    /// Where the top-left corner of the block was pasted
+    pub fn destination(&self) -> Address {
+        Address::new(self.destination_rect.left, self.destination_rect.top)
+    }
+
+    /// How `a1` should be rewritten after the move, or `None` if it now refers to cells that
+    /// were overwritten (a `#REF!`).  References to other sheets are returned as-is.
+    pub fn remap(&self, a1: &A1) -> Option<A1> {
+        if !sheet_name::is_same_sheet(a1.sheet_name.as_deref(), self.sheet_name.as_deref()) {
+            return Some(a1.clone());
+        }
+
+        Some(A1 {
+            sheet_name: a1.sheet_name.clone(),
+            reference: self.remap_reference(&a1.reference)?,
+        })
+    }
+
+    /// Like `remap` but for a reference on the same sheet as the move.  The members of a
+    /// `NonContiguous` are remapped individually and if any of them were overwritten the whole
+    /// thing is a `#REF!`.
+    pub fn remap_reference(&self, reference: &RangeOrCell) -> Option<RangeOrCell> {
+        if let RangeOrCell::NonContiguous(range_or_cells) = reference {
+            return Some(RangeOrCell::NonContiguous(
+                range_or_cells
+                    .iter()
+                    .map(|r| self.remap_reference(r))
+                    .collect::<Option<_>>()?,
+            ));
+        }
+
+        let rect = reference.rects()[0];
+        if self.source_rect.contains(&rect) {
+            // the destination is on the grid, so everything inside of the source will be too
+            reference.offset(self.rows, self.columns).ok()
+        } else if self.destination_rect.contains(&rect) {
+            None
+        } else {
+            Some(reference.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use std::str::FromStr;
+
+    fn r(s: &str) -> RangeOrCell {
+        RangeOrCell::from_str(s).unwrap()
+    }
+
+    fn b2_d5_to_h10() -> RangeMove {
+        RangeMove::new(r("B2:D5"), Address::new(7, 9), None).unwrap()
+    }
+
+    #[test]
+    fn accessors() {
+        let m = RangeMove::new(r("B2:D5"), Address::new(7, 9), Some("Sheet1")).unwrap();
+
+        assert_eq!(m.sheet_name(), Some("Sheet1"));
+        assert_eq!(m.source(), &r("B2:D5"));
+        assert_eq!(m.destination(), Address::new(7, 9));
+    }
+
+    #[test]
+    fn new_non_contiguous() {
+        assert!(RangeMove::new(r("A1,C3"), Address::new(5, 5), None).is_err());
+    }
+
+    #[test]
+    fn new_off_grid() {
+        assert!(RangeMove::new(r("A1:A2"), Address::new(0, usize::MAX), None).is_err());
+    }
+
+    #[test]
+    fn new_column_range() {
+        assert!(RangeMove::new(r("B:C"), Address::new(4, 0), None).is_ok());
+        assert!(RangeMove::new(r("B:C"), Address::new(4, 1), None).is_err());
+        assert!(RangeMove::new(r("2:3"), Address::new(0, 5), None).is_ok());
+        assert!(RangeMove::new(r("2:3"), Address::new(1, 5), None).is_err());
+    }
+
+    #[test]
+    fn remap_reference_moved() {
+        let m = b2_d5_to_h10();
+
+        assert_eq!(m.remap_reference(&r("B2")), Some(r("H10")));
+        assert_eq!(m.remap_reference(&r("$C$3:D5")), Some(r("$I$11:J13")));
+        assert_eq!(m.remap_reference(&r("B2:D5")), Some(r("H10:J13")));
+    }
+
+    #[test]
+    fn remap_reference_overwritten() {
+        let m = b2_d5_to_h10();
+
+        assert_eq!(m.remap_reference(&r("H10")), None);
+        assert_eq!(m.remap_reference(&r("I11:J13")), None);
+    }
+
+    #[test]
+    fn remap_reference_untouched() {
+        let m = b2_d5_to_h10();
+
+        assert_eq!(m.remap_reference(&r("A1")), Some(r("A1")));
+        // partially overlapping the source or destination
+        assert_eq!(m.remap_reference(&r("A1:C3")), Some(r("A1:C3")));
+        assert_eq!(m.remap_reference(&r("J13:K14")), Some(r("J13:K14")));
+        assert_eq!(m.remap_reference(&r("B:B")), Some(r("B:B")));
+    }
+
+    #[test]
+    fn remap_reference_overlapping_move() {
+        // shifting A1:A3 down one row overwrites A4
+        let m = RangeMove::new(r("A1:A3"), Address::new(0, 1), None).unwrap();
+
+        assert_eq!(m.remap_reference(&r("A2:A3")), Some(r("A3:A4")));
+        assert_eq!(m.remap_reference(&r("A4")), None);
+        assert_eq!(m.remap_reference(&r("A3:A4")), None);
+        assert_eq!(m.remap_reference(&r("A1:A4")), Some(r("A1:A4")));
+    }
+
+    #[test]
+    fn remap_reference_whole_columns() {
+        let m = RangeMove::new(r("B:C"), Address::new(5, 0), None).unwrap();
+
+        assert_eq!(m.remap_reference(&r("B:B")), Some(r("F:F")));
+        assert_eq!(m.remap_reference(&r("C5:C10")), Some(r("G5:G10")));
+        assert_eq!(m.remap_reference(&r("G7")), None);
+        assert_eq!(m.remap_reference(&r("3:3")), Some(r("3:3")));
+    }
+
+    #[test]
+    fn remap_reference_non_contiguous() {
+        let m = b2_d5_to_h10();
+
+        assert_eq!(
+            m.remap_reference(&r("B2,A1")),
+            Some(RangeOrCell::NonContiguous(vec![r("H10"), r("A1")]))
+        );
+        assert_eq!(m.remap_reference(&r("B2,A1,H10")), None);
+        assert_eq!(m.remap_reference(&r("H10,I11")), None);
+    }
+
+    #[test]
+    fn remap() {
+        let m = RangeMove::new(r("B2:D5"), Address::new(7, 9), Some("Foo")).unwrap();
+
+        assert_eq!(
+            m.remap(&new("Foo!B2").unwrap()),
+            Some(new("Foo!H10").unwrap())
+        );
+        assert_eq!(
+            m.remap(&new("Bar!B2").unwrap()),
+            Some(new("Bar!B2").unwrap())
+        );
+        assert_eq!(m.remap(&new("B2").unwrap()), Some(new("B2").unwrap()));
+        assert_eq!(m.remap(&new("Foo!H10").unwrap()), None);
+    }
+
+    #[test]
+    fn remap_sheet_name_case_insensitive() {
+        let m = RangeMove::new(r("B2:D5"), Address::new(7, 9), Some("Data")).unwrap();
+
+        assert_eq!(
+            m.remap(&new("data!B2").unwrap()),
+            Some(new("data!H10").unwrap())
+        );
+        assert_eq!(m.remap(&new("DATA!H10").unwrap()), None);
+    }
+}
@@ -0,0 +1,214 @@
+//! Setting and cycling the `$`s of a reference, like pressing F4 in a spreadsheet's formula bar.
+use super::RangeOrCell;
+use crate::{Column, Row};
+
+fn column_absolute(column: &Column, absolute: Option<bool>) -> Column {
+    absolute.map_or(*column, |a| column.with_absolute(a))
+}
+
+fn row_absolute(row: &Row, absolute: Option<bool>) -> Row {
+    absolute.map_or(*row, |a| row.with_absolute(a))
+}
+
+impl RangeOrCell {
+    /// The next step of the F4 cycle: `A1` -> `$A$1` -> `A$1` -> `$A1` -> `A1`.  A range moves
+    /// both of its ends to the step after the one its `from` is at (so `$A1:B2` becomes
+    /// `A1:B2`), while a `ColumnRange` or `RowRange` toggles between `A:B` and `$A:$B` (or `1:2`
+    /// and `$1:$2`).  Every member of a `NonContiguous` is cycled on its own.
+    pub fn cycle_absolute(&self) -> Self {
+        match self {
+            Self::Cell(a) => Self::Cell(a.cycle_absolute()),
+
+            Self::ColumnRange { from, .. } => self.with_absolute(Some(!from.absolute), None),
+
+            Self::NonContiguous(range_or_cells) => {
+                Self::NonContiguous(range_or_cells.iter().map(Self::cycle_absolute).collect())
+            }
+
+            Self::Range { from, .. } => {
+                let next = from.cycle_absolute();
+                self.with_absolute(Some(next.column.absolute), Some(next.row.absolute))
+            }
+
+            Self::RowRange { from, .. } => self.with_absolute(None, Some(!from.absolute)),
+        }
+    }
+
+    /// Make every part of the reference absolute: `$A$1:$B$2`
+    pub fn make_absolute(&self) -> Self {
+        self.with_absolute(Some(true), Some(true))
+    }
+
+    /// Make every part of the reference relative: `A1:B2`
+    pub fn make_relative(&self) -> Self {
+        self.with_absolute(Some(false), Some(false))
+    }
+
+    /// Set whether the columns and rows at both ends of the reference are `absolute`.  A `None`
+    /// leaves that axis as it is (and a `ColumnRange` has no rows to set, and vice versa).
+    pub fn with_absolute(&self, column: Option<bool>, row: Option<bool>) -> Self {
+        self.with_ends_absolute(true, true, column, row)
+    }
+
+    /// Like `with_absolute` but only for the `from` end of a range.  For a `Cell` that's the
+    /// cell itself.
+    pub fn with_from_absolute(&self, column: Option<bool>, row: Option<bool>) -> Self {
+        self.with_ends_absolute(true, false, column, row)
+    }
+
+    /// Like `with_absolute` but only for the `to` end of a range.  For a `Cell` that's the cell
+    /// itself.
+    pub fn with_to_absolute(&self, column: Option<bool>, row: Option<bool>) -> Self {
+        self.with_ends_absolute(false, true, column, row)
+    }
+
+    fn with_ends_absolute(
+        &self,
+        from_end: bool,
+        to_end: bool,
+        column: Option<bool>,
+        row: Option<bool>,
+    ) -> Self {
+        let (from_column, from_row) = if from_end {
+            (column, row)
+        } else {
+            (None, None)
+        };
+        let (to_column, to_row) = if to_end { (column, row) } else { (None, None) };
+
+        match self {
+            Self::Cell(a) => Self::Cell(a.with_absolute(column, row)),
+
+            Self::ColumnRange { from, to } => Self::ColumnRange {
+                from: column_absolute(from, from_column),
+                to: column_absolute(to, to_column),
+            },
+
+            Self::NonContiguous(range_or_cells) => Self::NonContiguous(
+                range_or_cells
+                    .iter()
+                    .map(|r| r.with_ends_absolute(from_end, to_end, column, row))
+                    .collect(),
+            ),
+
+            Self::Range { from, to } => Self::Range {
+                from: from.with_absolute(from_column, from_row),
+                to: to.with_absolute(to_column, to_row),
+            },
+
+            Self::RowRange { from, to } => Self::RowRange {
+                from: row_absolute(from, from_row),
+                to: row_absolute(to, to_row),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn cycle(s: &str) -> Vec<String> {
+        let r = RangeOrCell::from_str(s).unwrap();
+        std::iter::successors(Some(r), |r| Some(r.cycle_absolute()))
+            .take(5)
+            .map(|r| r.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn cycle_absolute_cell() {
+        assert_eq!(cycle("A1"), vec!["A1", "$A$1", "A$1", "$A1", "A1"]);
+    }
+
+    #[test]
+    fn cycle_absolute_range() {
+        assert_eq!(
+            cycle("A1:B2"),
+            vec!["A1:B2", "$A$1:$B$2", "A$1:B$2", "$A1:$B2", "A1:B2"]
+        );
+        // the `from` end decides where in the cycle it is
+        assert_eq!(cycle("$A1:B2")[1], "A1:B2");
+    }
+
+    #[test]
+    fn cycle_absolute_column_and_row_ranges() {
+        assert_eq!(cycle("A:B")[..3], ["A:B", "$A:$B", "A:B"]);
+        assert_eq!(cycle("1:2")[..3], ["1:2", "$1:$2", "1:2"]);
+    }
+
+    #[test]
+    fn cycle_absolute_non_contiguous() {
+        assert_eq!(
+            RangeOrCell::from_str("A1,$B$2").unwrap().cycle_absolute(),
+            RangeOrCell::NonContiguous(vec![
+                RangeOrCell::from_str("$A$1").unwrap(),
+                RangeOrCell::from_str("B$2").unwrap(),
+            ])
+        );
+    }
+
+    #[test]
+    fn make_absolute_and_relative() {
+        let r = RangeOrCell::from_str("A$1:$B2").unwrap();
+
+        assert_eq!(r.make_absolute().to_string(), "$A$1:$B$2");
+        assert_eq!(r.make_relative().to_string(), "A1:B2");
+        assert_eq!(
+            RangeOrCell::from_str("A:B")
+                .unwrap()
+                .make_absolute()
+                .to_string(),
+            "$A:$B"
+        );
+        assert_eq!(
+            RangeOrCell::from_str("$1:$2")
+                .unwrap()
+                .make_relative()
+                .to_string(),
+            "1:2"
+        );
+    }
+
+    #[test]
+    fn with_absolute_per_axis() {
+        let r = RangeOrCell::from_str("A1:B2").unwrap();
+
+        assert_eq!(r.with_absolute(Some(true), None).to_string(), "$A1:$B2");
+        assert_eq!(r.with_absolute(None, Some(true)).to_string(), "A$1:B$2");
+        // rows don't mean anything to a column range
+        assert_eq!(
+            RangeOrCell::from_str("A:B")
+                .unwrap()
+                .with_absolute(None, Some(true))
+                .to_string(),
+            "A:B"
+        );
+    }
+
+    #[test]
+    fn with_from_and_to_absolute() {
+        let r = RangeOrCell::from_str("A1:B2").unwrap();
+
+        assert_eq!(
+            r.with_from_absolute(Some(true), Some(true)).to_string(),
+            "$A$1:B2"
+        );
+        assert_eq!(r.with_to_absolute(None, Some(true)).to_string(), "A1:B$2");
+        assert_eq!(
+            RangeOrCell::from_str("1:2")
+                .unwrap()
+                .with_to_absolute(None, Some(true))
+                .to_string(),
+            "1:$2"
+        );
+        assert_eq!(
+            RangeOrCell::from_str("A1")
+                .unwrap()
+                .with_to_absolute(Some(true), None)
+                .to_string(),
+            "$A1"
+        );
+    }
+}
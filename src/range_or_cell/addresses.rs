@@ -0,0 +1,448 @@
+//! Iterating over the individual cells of a reference as plain `Address`es.  Unlike `iter`,
+//! this borrows the reference rather than cloning it and doesn't allocate as it goes (other
+//! than for a `NonContiguous` nested inside of another one).
+use super::iterator::{span, step};
+use super::RangeOrCell;
+use crate::{Address, Column, Index, Row, Traversal};
+use std::{iter, slice};
+
+/// The first and last cells of a contiguous reference.  A `ColumnRange` runs down to the last
+/// row and a `RowRange` across to the last column.
+pub(super) fn endpoints(range_or_cell: &RangeOrCell) -> (Address, Address) {
+    match range_or_cell {
+        RangeOrCell::Cell(a) => (*a, *a),
+
+        RangeOrCell::ColumnRange { from, to } => (
+            Address {
+                column: *from,
+                row: Row::new(0),
+            },
+            Address {
+                column: *to,
+                row: Row::new(Index::MAX),
+            },
+        ),
+
+        RangeOrCell::Range { from, to } => (*from, *to),
+
+        RangeOrCell::RowRange { from, to } => (
+            Address {
+                column: Column::new(0),
+                row: *from,
+            },
+            Address {
+                column: Column::new(Index::MAX),
+                row: *to,
+            },
+        ),
+
+        RangeOrCell::NonContiguous(_) => unreachable!("NonContiguous is iterated per member"),
+    }
+}
+
+/// The cells of a single contiguous reference.  Rather than working out each cell from its
+/// position (which is a division per cell) this keeps a cursor of how far along the current row
+/// (or column) it is and which row (or column) that is.
+#[derive(Clone, Debug)]
+struct Cells {
+    from: Address,
+    to: Address,
+    traversal: Traversal,
+    outer_len: u128,
+    inner_len: u128,
+    outer: u128,
+    inner: u128,
+    remaining: u128,
+}
+
+impl Cells {
+    fn new(range_or_cell: &RangeOrCell, traversal: Traversal) -> Self {
+        let (from, to) = endpoints(range_or_cell);
+        let (width, height) = (span(from.column.x, to.column.x), span(from.row.y, to.row.y));
+        let (outer_len, inner_len) = if traversal.is_column_major() {
+            (width, height)
+        } else {
+            (height, width)
+        };
+
+        Self {
+            from,
+            to,
+            traversal,
+            outer_len,
+            inner_len,
+            outer: 0,
+            inner: 0,
+            remaining: width.saturating_mul(height),
+        }
+    }
+
+    fn nth(&mut self, n: u128) -> Option<Address> {
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+
+        if n > 0 {
+            self.outer += n / self.inner_len;
+            self.inner += n % self.inner_len;
+            if self.inner >= self.inner_len {
+                self.inner -= self.inner_len;
+                self.outer += 1;
+            }
+        }
+        let address = self.cell();
+
+        self.remaining -= n + 1;
+        self.inner += 1;
+        if self.inner == self.inner_len {
+            self.inner = 0;
+            self.outer += 1;
+        }
+
+        Some(address)
+    }
+
+    /// The cell under the cursor
+    fn cell(&self) -> Address {
+        let (mut outer, mut inner) = (self.outer, self.inner);
+        if self.traversal.is_serpentine() && outer % 2 == 1 {
+            inner = self.inner_len - 1 - inner;
+        }
+        if self.traversal.is_reverse() {
+            outer = self.outer_len - 1 - outer;
+            inner = self.inner_len - 1 - inner;
+        }
+
+        let (columns, rows) = if self.traversal.is_column_major() {
+            (outer, inner)
+        } else {
+            (inner, outer)
+        };
+
+        Address {
+            column: self
+                .from
+                .column
+                .with_x(step(self.from.column.x, self.to.column.x, columns)),
+            row: self
+                .from
+                .row
+                .with_y(step(self.from.row.y, self.to.row.y, rows)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Member<'a> {
+    Cells(Cells),
+    Nested(Box<Addresses<'a>>),
+}
+
+impl Member<'_> {
+    fn remaining(&self) -> u128 {
+        match self {
+            Self::Cells(c) => c.remaining,
+            Self::Nested(a) => a.remaining(),
+        }
+    }
+
+    fn nth(&mut self, n: u128) -> Option<Address> {
+        match self {
+            Self::Cells(c) => c.nth(n),
+            Self::Nested(a) => a.nth_address(n),
+        }
+    }
+}
+
+/// How many cells there are in a reference, counting any overlaps between the members of a
+/// `NonContiguous` more than once
+pub(super) fn cell_count(range_or_cell: &RangeOrCell) -> u128 {
+    match range_or_cell {
+        RangeOrCell::NonContiguous(range_or_cells) => range_or_cells
+            .iter()
+            .fold(0, |n, r| n.saturating_add(cell_count(r))),
+        r => Cells::new(r, Traversal::RowMajor).remaining,
+    }
+}
+
+/// The cells of a `RangeOrCell`, as returned by `RangeOrCell::addresses`.
+#[derive(Clone, Debug)]
+pub struct Addresses<'a> {
+    members: &'a [RangeOrCell],
+    traversal: Traversal,
+    i: usize,
+    current: Option<Member<'a>>,
+}
+
+impl<'a> Addresses<'a> {
+    fn new(range_or_cell: &'a RangeOrCell, traversal: Traversal) -> Self {
+        Self {
+            members: match range_or_cell {
+                RangeOrCell::NonContiguous(range_or_cells) => range_or_cells,
+                r => slice::from_ref(r),
+            },
+            traversal,
+            i: 0,
+            current: None,
+        }
+    }
+
+    /// The members left to start on, in the order they'll be visited
+    fn upcoming(&self) -> impl Iterator<Item = &'a RangeOrCell> {
+        let (reverse, i) = (self.traversal.is_reverse(), self.i);
+        let members = self.members;
+
+        // going backwards visits the members from the last one
+        (i..members.len()).map(move |i| {
+            if reverse {
+                &members[members.len() - 1 - i]
+            } else {
+                &members[i]
+            }
+        })
+    }
+
+    fn remaining(&self) -> u128 {
+        self.upcoming().fold(
+            self.current.as_ref().map_or(0, Member::remaining),
+            |n, r| n.saturating_add(cell_count(r)),
+        )
+    }
+
+    fn nth_address(&mut self, mut n: u128) -> Option<Address> {
+        loop {
+            if let Some(member) = &mut self.current {
+                let remaining = member.remaining();
+                if n < remaining {
+                    return member.nth(n);
+                }
+                n -= remaining;
+            }
+            self.current = None;
+
+            let r = self.upcoming().next()?;
+            self.i += 1;
+
+            // skip over whole members without starting on them
+            let count = cell_count(r);
+            if n >= count {
+                n -= count;
+                continue;
+            }
+
+            self.current = Some(match r {
+                RangeOrCell::NonContiguous(_) => {
+                    Member::Nested(Box::new(Addresses::new(r, self.traversal)))
+                }
+                r => Member::Cells(Cells::new(r, self.traversal)),
+            });
+        }
+    }
+}
+
+impl iter::Iterator for Addresses<'_> {
+    type Item = Address;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the common case of being part-way through a range
+        if let Some(Member::Cells(cells)) = &mut self.current {
+            if cells.remaining > 0 {
+                return cells.nth(0);
+            }
+        }
+
+        self.nth_address(0)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.nth_address(n as u128)
+    }
+
+    /// Exact, unless there are more than `usize::MAX` cells left
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining()) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl iter::FusedIterator for Addresses<'_> {}
+
+/// The `(row, column)` indexes of the cells of a `RangeOrCell`, as returned by
+/// `RangeOrCell::coordinates`.
+#[derive(Clone, Debug)]
+pub struct Coordinates<'a> {
+    addresses: Addresses<'a>,
+}
+
+impl iter::Iterator for Coordinates<'_> {
+    type Item = (Index, Index);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = self.addresses.next()?;
+        Some((a.row.y, a.column.x))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let a = self.addresses.nth(n)?;
+        Some((a.row.y, a.column.x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.addresses.size_hint()
+    }
+}
+
+impl iter::FusedIterator for Coordinates<'_> {}
+
+impl RangeOrCell {
+    /// Every cell of the reference, row-by-row.  Each cell keeps the `absolute` flags of the end
+    /// of the range it starts from.  A `ColumnRange` or `RowRange` goes on until the edge of the
+    /// grid, so you'll want to `take` from those.  Overlapping members of a `NonContiguous` will
+    /// emit the same cell more than once.
+    pub fn addresses(&self) -> Addresses<'_> {
+        self.addresses_with(Traversal::RowMajor)
+    }
+
+    /// Every cell of the reference in the given `traversal` order.  See `iter_with`.
+    pub fn addresses_with(&self, traversal: Traversal) -> Addresses<'_> {
+        Addresses::new(self, traversal)
+    }
+
+    /// The `(row, column)` indexes of every cell of the reference, row-by-row.  See `addresses`.
+    pub fn coordinates(&self) -> Coordinates<'_> {
+        Coordinates {
+            addresses: self.addresses(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn addresses_to_strs(r: &str) -> Vec<String> {
+        RangeOrCell::from_str(r)
+            .unwrap()
+            .addresses()
+            .map(|a| a.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn addresses_matches_iter() {
+        for r in ["B2", "A1:C3", "C3:A1", "$A$1:B2", "A1:B2,D4", "A1,B2:C3,E5"] {
+            let r = RangeOrCell::from_str(r).unwrap();
+
+            assert_eq!(
+                r.addresses().map(RangeOrCell::Cell).collect::<Vec<_>>(),
+                r.iter().collect::<Vec<_>>(),
+                "{r}"
+            );
+        }
+    }
+
+    #[test]
+    fn addresses_with() {
+        let r = RangeOrCell::from_str("A1:B2,D4").unwrap();
+
+        for traversal in [
+            Traversal::ColumnMajor,
+            Traversal::ReverseRowMajor,
+            Traversal::ReverseColumnMajor,
+            Traversal::RowSerpentine,
+        ] {
+            assert_eq!(
+                r.addresses_with(traversal)
+                    .map(RangeOrCell::Cell)
+                    .collect::<Vec<_>>(),
+                r.iter_with(traversal).collect::<Vec<_>>(),
+                "{traversal:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn addresses_with_nth() {
+        let r = RangeOrCell::from_str("B2:E4,G1:H2").unwrap();
+
+        for traversal in [
+            Traversal::RowMajor,
+            Traversal::ColumnMajor,
+            Traversal::ReverseRowMajor,
+            Traversal::ReverseColumnMajor,
+            Traversal::RowSerpentine,
+            Traversal::ColumnSerpentine,
+        ] {
+            assert_eq!(
+                r.addresses_with(traversal)
+                    .step_by(3)
+                    .map(RangeOrCell::Cell)
+                    .collect::<Vec<_>>(),
+                r.iter_with(traversal).step_by(3).collect::<Vec<_>>(),
+                "{traversal:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn addresses_column_and_row_ranges() {
+        let r = RangeOrCell::from_str("B:C").unwrap();
+        assert_eq!(
+            r.addresses()
+                .take(3)
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["B1", "C1", "B2"]
+        );
+
+        let r = RangeOrCell::from_str("2:2").unwrap();
+        assert_eq!(
+            r.addresses()
+                .take(2)
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["A2", "B2"]
+        );
+    }
+
+    #[test]
+    fn addresses_nested_non_contiguous() {
+        let r = RangeOrCell::NonContiguous(vec![
+            RangeOrCell::from_str("A1").unwrap(),
+            RangeOrCell::from_str("B1,C1:C2").unwrap(),
+        ]);
+
+        assert_eq!(
+            r.addresses().map(|a| a.to_string()).collect::<Vec<_>>(),
+            vec!["A1", "B1", "C1", "C2"]
+        );
+    }
+
+    #[test]
+    fn addresses_size_hint_and_nth() {
+        let r = RangeOrCell::from_str("A1:Z100000,AA1").unwrap();
+        let mut a = r.addresses();
+
+        assert_eq!(a.size_hint(), (2_600_001, Some(2_600_001)));
+        assert_eq!(a.nth(2_600_000).unwrap().to_string(), "AA1");
+        assert_eq!(a.size_hint(), (0, Some(0)));
+        assert_eq!(a.next(), None);
+
+        assert_eq!(addresses_to_strs("A1:B1,A1"), vec!["A1", "B1", "A1"]);
+    }
+
+    #[test]
+    fn coordinates() {
+        assert_eq!(
+            RangeOrCell::from_str("B2:C3")
+                .unwrap()
+                .coordinates()
+                .collect::<Vec<_>>(),
+            vec![(1, 1), (1, 2), (2, 1), (2, 2)]
+        );
+    }
+}
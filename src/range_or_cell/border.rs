@@ -0,0 +1,386 @@
+//! The edges of a reference and the cells around it - for flood fills, border rendering and the
+//! like.
+use super::{cell_or_range, RangeOrCell};
+use crate::{Address, Column, Connectivity, Direction, Index, Row};
+use std::{cmp, iter, vec};
+
+/// Walks the outline of a rectangle clockwise starting from its top-left corner.  We use `i128`s
+/// so that a ring around a range touching row 1 or column A can go to `-1` (which gets skipped).
+#[derive(Clone, Debug)]
+struct Outline {
+    left: i128,
+    top: i128,
+    right: i128,
+    bottom: i128,
+    skip_corners: bool,
+    k: i128,
+}
+
+impl Outline {
+    fn len(&self) -> i128 {
+        let (w, h) = (self.right - self.left + 1, self.bottom - self.top + 1);
+        if w == 1 || h == 1 {
+            w * h
+        } else {
+            2 * w + 2 * h - 4
+        }
+    }
+
+    /// The `k`th cell going clockwise around the outline
+    fn nth_cell(&self, k: i128) -> (i128, i128) {
+        let (w, h) = (self.right - self.left + 1, self.bottom - self.top + 1);
+
+        if k < w {
+            (self.left + k, self.top)
+        } else if k < w + h - 1 {
+            (self.right, self.top + 1 + (k - w))
+        } else if k < 2 * w + h - 2 {
+            (self.right - 1 - (k - w - h + 1), self.bottom)
+        } else {
+            (self.left, self.bottom - 1 - (k - (2 * w + h - 2)))
+        }
+    }
+
+    fn is_corner(&self, (x, y): (i128, i128)) -> bool {
+        (x == self.left || x == self.right) && (y == self.top || y == self.bottom)
+    }
+}
+
+impl iter::Iterator for Outline {
+    type Item = RangeOrCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.k < self.len() {
+            let (x, y) = self.nth_cell(self.k);
+            self.k += 1;
+
+            let off_grid = x < 0 || y < 0 || x > Index::MAX as i128 || y > Index::MAX as i128;
+            let skipped = self.skip_corners && self.is_corner((x, y));
+
+            if !off_grid && !skipped {
+                return Some(RangeOrCell::Cell(Address::new(x as Index, y as Index)));
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Walk {
+    Outline(Outline),
+
+    /// For the sides of `ColumnRange`s and `RowRange`s there's only ever a couple of items
+    Items(vec::IntoIter<RangeOrCell>),
+}
+
+/// The cells along (or just outside of) the border of a `RangeOrCell`, as returned by
+/// `RangeOrCell::perimeter` and `RangeOrCell::ring`.
+#[derive(Clone, Debug)]
+pub struct RangeOrCellBorder {
+    current: Option<Walk>,
+    walks: vec::IntoIter<Walk>,
+}
+
+impl iter::Iterator for RangeOrCellBorder {
+    type Item = RangeOrCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next = match &mut self.current {
+                Some(Walk::Outline(o)) => o.next(),
+                Some(Walk::Items(i)) => i.next(),
+                None => None,
+            };
+
+            if next.is_some() {
+                return next;
+            }
+
+            self.current = Some(self.walks.next()?);
+        }
+    }
+}
+
+fn outline(from: &Address, to: &Address, grow: i128, skip_corners: bool) -> Walk {
+    let (top_left, bottom_right) = Address::corners(from, to);
+
+    Walk::Outline(Outline {
+        left: top_left.column.x as i128 - grow,
+        top: top_left.row.y as i128 - grow,
+        right: bottom_right.column.x as i128 + grow,
+        bottom: bottom_right.row.y as i128 + grow,
+        skip_corners,
+        k: 0,
+    })
+}
+
+/// The distinct items in `items`, skipping the `None`s
+fn distinct(items: Vec<Option<RangeOrCell>>) -> Walk {
+    let mut distinct: Vec<RangeOrCell> = vec![];
+    for item in items.into_iter().flatten() {
+        if !distinct.contains(&item) {
+            distinct.push(item);
+        }
+    }
+
+    Walk::Items(distinct.into_iter())
+}
+
+impl RangeOrCell {
+    /// The row or column of cells along one side of the reference - for example the top row of
+    /// a `Range` for `Direction::Up`.  Returns `None` for the unbounded side of a `ColumnRange`
+    /// (`Direction::Down`) or `RowRange` (`Direction::Right`).  For a `NonContiguous` you get
+    /// the edges of each of its members.
+    pub fn edge(&self, direction: Direction) -> Option<Self> {
+        match self {
+            Self::Cell(_) => Some(self.clone()),
+
+            Self::ColumnRange { from, to } => {
+                let (left, right) = (cmp::min(*from, *to), cmp::max(*from, *to));
+                match direction {
+                    Direction::Up => Some(cell_or_range(
+                        Address {
+                            column: left,
+                            row: Row::new(0),
+                        },
+                        Address {
+                            column: right,
+                            row: Row::new(0),
+                        },
+                    )),
+                    Direction::Down => None,
+                    Direction::Left => Some(Self::column(left)),
+                    Direction::Right => Some(Self::column(right)),
+                }
+            }
+
+            Self::NonContiguous(range_or_cells) => {
+                let edges = range_or_cells
+                    .iter()
+                    .filter_map(|r| r.edge(direction))
+                    .collect::<Vec<_>>();
+
+                (!edges.is_empty()).then_some(Self::NonContiguous(edges))
+            }
+
+            Self::Range { from, to } => {
+                let (top_left, bottom_right) = Address::corners(from, to);
+                Some(match direction {
+                    Direction::Up => cell_or_range(
+                        top_left,
+                        Address {
+                            row: top_left.row,
+                            ..bottom_right
+                        },
+                    ),
+                    Direction::Down => cell_or_range(
+                        Address {
+                            row: bottom_right.row,
+                            ..top_left
+                        },
+                        bottom_right,
+                    ),
+                    Direction::Left => cell_or_range(
+                        top_left,
+                        Address {
+                            column: top_left.column,
+                            ..bottom_right
+                        },
+                    ),
+                    Direction::Right => cell_or_range(
+                        Address {
+                            column: bottom_right.column,
+                            ..top_left
+                        },
+                        bottom_right,
+                    ),
+                })
+            }
+
+            Self::RowRange { from, to } => {
+                let (top, bottom) = (cmp::min(*from, *to), cmp::max(*from, *to));
+                match direction {
+                    Direction::Up => Some(Self::row(top)),
+                    Direction::Down => Some(Self::row(bottom)),
+                    Direction::Left => Some(cell_or_range(
+                        Address {
+                            column: Column::new(0),
+                            row: top,
+                        },
+                        Address {
+                            column: Column::new(0),
+                            row: bottom,
+                        },
+                    )),
+                    Direction::Right => None,
+                }
+            }
+        }
+    }
+
+    /// The cells along the inside of the border, each one once, going clockwise from the
+    /// top-left corner.  A `ColumnRange` emits its first and last columns (and a `RowRange` its
+    /// first and last rows), which are the sides that separate it from the rest of the sheet.
+    /// Each member of a `NonContiguous` is walked in turn.
+    pub fn perimeter(&self) -> RangeOrCellBorder {
+        self.border(|r| match r {
+            Self::Cell(a) => outline(a, a, 0, false),
+            Self::Range { from, to } => outline(from, to, 0, false),
+            Self::ColumnRange { .. } => {
+                distinct(vec![r.edge(Direction::Left), r.edge(Direction::Right)])
+            }
+            Self::RowRange { .. } => distinct(vec![r.edge(Direction::Up), r.edge(Direction::Down)]),
+            Self::NonContiguous(_) => unreachable!("NonContiguous is walked per member"),
+        })
+    }
+
+    /// The cells just outside of the border, going clockwise from the top-left corner and
+    /// skipping any that would be off of the grid.  With `Connectivity::Four` the diagonal
+    /// corners are left out.  A `ColumnRange` emits the columns on either side of it (and a
+    /// `RowRange` the rows above and below it).  Each member of a `NonContiguous` is walked in
+    /// turn, so the ring of one member might overlap another.
+    pub fn ring(&self, connectivity: Connectivity) -> RangeOrCellBorder {
+        let skip_corners = connectivity == Connectivity::Four;
+
+        self.border(|r| match r {
+            Self::Cell(a) => outline(a, a, 1, skip_corners),
+            Self::Range { from, to } => outline(from, to, 1, skip_corners),
+            Self::ColumnRange { from, to } => distinct(vec![
+                cmp::min(from, to).offset(-1).map(Self::column),
+                cmp::max(from, to).offset(1).map(Self::column),
+            ]),
+            Self::RowRange { from, to } => distinct(vec![
+                cmp::min(from, to).offset(-1).map(Self::row),
+                cmp::max(from, to).offset(1).map(Self::row),
+            ]),
+            Self::NonContiguous(_) => unreachable!("NonContiguous is walked per member"),
+        })
+    }
+
+    fn border<F: Fn(&Self) -> Walk>(&self, f: F) -> RangeOrCellBorder {
+        fn walks<F: Fn(&RangeOrCell) -> Walk>(r: &RangeOrCell, f: &F, acc: &mut Vec<Walk>) {
+            if let RangeOrCell::NonContiguous(range_or_cells) = r {
+                for r in range_or_cells {
+                    walks(r, f, acc);
+                }
+            } else {
+                acc.push(f(r));
+            }
+        }
+
+        let mut acc = vec![];
+        walks(self, &f, &mut acc);
+
+        RangeOrCellBorder {
+            current: None,
+            walks: acc.into_iter(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn r(s: &str) -> RangeOrCell {
+        RangeOrCell::from_str(s).unwrap()
+    }
+
+    fn to_strs<I: Iterator<Item = RangeOrCell>>(i: I) -> Vec<String> {
+        i.map(|r| r.to_string()).collect()
+    }
+
+    #[test]
+    fn edge_column_range() {
+        assert_eq!(r("B:D").edge(Direction::Up), Some(r("B1:D1")));
+        assert_eq!(r("B:D").edge(Direction::Down), None);
+        assert_eq!(r("B:D").edge(Direction::Right), Some(r("D:D")));
+    }
+
+    #[test]
+    fn edge_non_contiguous() {
+        assert_eq!(
+            r("A1:B2,C:D").edge(Direction::Down),
+            Some(RangeOrCell::NonContiguous(vec![r("A2:B2")]))
+        );
+        assert_eq!(r("C:D").edge(Direction::Down), None);
+    }
+
+    #[test]
+    fn edge_range() {
+        assert_eq!(r("D4:B2").edge(Direction::Up), Some(r("B2:D2")));
+        assert_eq!(r("B2:D4").edge(Direction::Down), Some(r("B4:D4")));
+        assert_eq!(r("B2:D4").edge(Direction::Left), Some(r("B2:B4")));
+        assert_eq!(r("B2:D4").edge(Direction::Right), Some(r("D2:D4")));
+        assert_eq!(r("B2:B4").edge(Direction::Right), Some(r("B2:B4")));
+    }
+
+    #[test]
+    fn edge_row_range() {
+        assert_eq!(r("2:4").edge(Direction::Left), Some(r("A2:A4")));
+        assert_eq!(r("2:4").edge(Direction::Right), None);
+        assert_eq!(r("2:4").edge(Direction::Down), Some(r("4:4")));
+    }
+
+    #[test]
+    fn perimeter_cell() {
+        assert_eq!(to_strs(r("B2").perimeter()), vec!["B2"]);
+    }
+
+    #[test]
+    fn perimeter_range() {
+        assert_eq!(
+            to_strs(r("A1:C3").perimeter()),
+            vec!["A1", "B1", "C1", "C2", "C3", "B3", "A3", "A2"]
+        );
+        assert_eq!(to_strs(r("A1:A3").perimeter()), vec!["A1", "A2", "A3"]);
+        assert_eq!(to_strs(r("A1:C1").perimeter()), vec!["A1", "B1", "C1"]);
+    }
+
+    #[test]
+    fn perimeter_column_and_row_ranges() {
+        assert_eq!(to_strs(r("B:D").perimeter()), vec!["B:B", "D:D"]);
+        assert_eq!(to_strs(r("B:B").perimeter()), vec!["B:B"]);
+        assert_eq!(to_strs(r("2:4").perimeter()), vec!["2:2", "4:4"]);
+    }
+
+    #[test]
+    fn perimeter_non_contiguous() {
+        assert_eq!(
+            to_strs(r("A1,C3:D4").perimeter()),
+            vec!["A1", "C3", "D3", "D4", "C4"]
+        );
+    }
+
+    #[test]
+    fn ring_cell() {
+        assert_eq!(
+            to_strs(r("B2").ring(Connectivity::Eight)),
+            vec!["A1", "B1", "C1", "C2", "C3", "B3", "A3", "A2"]
+        );
+        assert_eq!(
+            to_strs(r("B2").ring(Connectivity::Four)),
+            vec!["B1", "C2", "B3", "A2"]
+        );
+    }
+
+    #[test]
+    fn ring_range_clipped() {
+        assert_eq!(
+            to_strs(r("A1:B2").ring(Connectivity::Eight)),
+            vec!["C1", "C2", "C3", "B3", "A3"]
+        );
+    }
+
+    #[test]
+    fn ring_column_and_row_ranges() {
+        assert_eq!(
+            to_strs(r("B:D").ring(Connectivity::Four)),
+            vec!["A:A", "E:E"]
+        );
+        assert_eq!(to_strs(r("A:D").ring(Connectivity::Four)), vec!["E:E"]);
+        assert_eq!(to_strs(r("1:1").ring(Connectivity::Eight)), vec!["2:2"]);
+    }
+}
@@ -0,0 +1,476 @@
+//! Splitting a reference up into smaller pieces, for APIs which cap how much you can read or
+//! write in a single request (or for processing a block one row or column at a time).
+use super::{cell_or_range, RangeOrCell};
+use crate::rect::Rect;
+use crate::{Address, Column, Error, Index, Result, Row};
+use std::{cmp, iter, vec};
+
+/// How big each chunk produced by `RangeOrCell::chunks` can be.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ChunkSize {
+    /// Full-width bands of at most this many rows
+    Rows(Index),
+
+    /// Full-height bands of at most this many columns
+    Columns(Index),
+
+    /// At most this many cells.  Chunks are made of whole rows when a row fits, otherwise each
+    /// row is split into pieces.
+    Cells(Index),
+
+    /// Tiles of at most `height` rows by `width` columns
+    Tiles { height: Index, width: Index },
+}
+
+/// The inclusive `(start, end)` indexes of a reference along one axis, or `None` if it's
+/// unbounded along it.
+type Span = Option<(Index, Index)>;
+
+/// Walks the tiles of a single (contiguous) reference, left-to-right then top-to-bottom.
+#[derive(Clone, Debug)]
+struct Tiling {
+    columns: Span,
+    rows: Span,
+    width: Option<Index>,
+    height: Option<Index>,
+    current: Option<(Index, Index)>,
+}
+
+fn span_len((start, end): (Index, Index)) -> Index {
+    end - start + 1
+}
+
+fn chunk_end(start: Index, span: Span, size: Option<Index>) -> Index {
+    match (span, size) {
+        (Some((_, end)), Some(size)) => cmp::min(start.saturating_add(size - 1), end),
+        (Some((_, end)), None) => end,
+        // there's only ever a single chunk along an unbounded axis so it doesn't matter
+        (None, _) => start,
+    }
+}
+
+impl Tiling {
+    fn new(range_or_cell: &RangeOrCell, size: ChunkSize) -> Result<Self> {
+        let (columns, rows) = match range_or_cell {
+            RangeOrCell::Cell(a) => (Some((a.column.x, a.column.x)), Some((a.row.y, a.row.y))),
+
+            RangeOrCell::ColumnRange { from, to } => {
+                (Some((cmp::min(from.x, to.x), cmp::max(from.x, to.x))), None)
+            }
+
+            RangeOrCell::Range { from, to } => {
+                let (top_left, bottom_right) = Address::corners(from, to);
+                (
+                    Some((top_left.column.x, bottom_right.column.x)),
+                    Some((top_left.row.y, bottom_right.row.y)),
+                )
+            }
+
+            RangeOrCell::RowRange { from, to } => {
+                (None, Some((cmp::min(from.y, to.y), cmp::max(from.y, to.y))))
+            }
+
+            RangeOrCell::NonContiguous(_) => unreachable!("NonContiguous is chunked per member"),
+        };
+
+        let (height, width) = match size {
+            ChunkSize::Rows(n) => (Some(n), None),
+            ChunkSize::Columns(n) => (None, Some(n)),
+            ChunkSize::Tiles { height, width } => (Some(height), Some(width)),
+            ChunkSize::Cells(n) => match columns {
+                Some(c) if rows.is_some() && span_len(c) <= n => (Some(n / span_len(c)), None),
+                _ => (Some(1), Some(n)),
+            },
+        };
+
+        if height == Some(0) || width == Some(0) {
+            return Err(Error::invalid_argument(
+                range_or_cell,
+                format!("Chunks must be at least one cell in size, not {size:?}"),
+            ));
+        } else if (rows.is_none() && height.is_some()) || (columns.is_none() && width.is_some()) {
+            return Err(Error::invalid_argument(
+                range_or_cell,
+                format!("Can not split an unbounded reference by {size:?}"),
+            ));
+        }
+
+        Ok(Self {
+            columns,
+            rows,
+            width,
+            height,
+            current: Some((
+                columns.map_or(0, |(start, _)| start),
+                rows.map_or(0, |(start, _)| start),
+            )),
+        })
+    }
+}
+
+impl iter::Iterator for Tiling {
+    type Item = RangeOrCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.current?;
+        let x_end = chunk_end(x, self.columns, self.width);
+        let y_end = chunk_end(y, self.rows, self.height);
+
+        self.current = match (self.columns, self.rows) {
+            (Some((_, end)), _) if x_end < end => Some((x_end + 1, y)),
+            (columns, Some((_, end))) if y_end < end => {
+                Some((columns.map_or(0, |(start, _)| start), y_end + 1))
+            }
+            _ => None,
+        };
+
+        Some(match (self.columns, self.rows) {
+            (None, _) => RangeOrCell::RowRange {
+                from: Row::new(y),
+                to: Row::new(y_end),
+            },
+            (_, None) => RangeOrCell::ColumnRange {
+                from: Column::new(x),
+                to: Column::new(x_end),
+            },
+            _ => cell_or_range(Address::new(x, y), Address::new(x_end, y_end)),
+        })
+    }
+}
+
+/// The chunks of a `RangeOrCell`, as returned by `RangeOrCell::chunks`.
+#[derive(Clone, Debug)]
+pub struct RangeOrCellChunks {
+    current: Option<Tiling>,
+    tilings: vec::IntoIter<Tiling>,
+}
+
+impl iter::Iterator for RangeOrCellChunks {
+    type Item = RangeOrCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(chunk) = self.current.as_mut().and_then(|t| t.next()) {
+                return Some(chunk);
+            }
+
+            self.current = Some(self.tilings.next()?);
+        }
+    }
+}
+
+fn tilings(range_or_cell: &RangeOrCell, size: ChunkSize, acc: &mut Vec<Tiling>) -> Result<()> {
+    if let RangeOrCell::NonContiguous(range_or_cells) = range_or_cell {
+        for r in range_or_cells {
+            tilings(r, size, acc)?;
+        }
+    } else {
+        acc.push(Tiling::new(range_or_cell, size)?);
+    }
+
+    Ok(())
+}
+
+/// The tilings for splitting `range_or_cell` into single rows or columns (`size` is
+/// `ChunkSize::Rows(1)` or `ChunkSize::Columns(1)`), optionally clipped to the sheet ending at
+/// `bounds`.
+fn slice_tilings(
+    range_or_cell: &RangeOrCell,
+    size: ChunkSize,
+    bounds: Option<&Address>,
+) -> Vec<Tiling> {
+    let sheet = bounds.map(|b| Rect::from(&Address::new(0, 0)).union(&b.into()));
+
+    range_or_cell
+        .rects()
+        .into_iter()
+        .filter_map(|rect| match sheet {
+            Some(sheet) => sheet.intersection(&rect),
+            None => Some(rect),
+        })
+        .map(|rect| {
+            let to = Address::new(rect.right, rect.bottom);
+            let r = match (size, rect.bottom, rect.right) {
+                // splitting whole rows into rows (or whole columns into columns) keeps them
+                // unbounded
+                (ChunkSize::Rows(_), _, Index::MAX) if sheet.is_none() => RangeOrCell::RowRange {
+                    from: Row::new(rect.top),
+                    to: Row::new(rect.bottom),
+                },
+                (ChunkSize::Columns(_), Index::MAX, _) if sheet.is_none() => {
+                    RangeOrCell::ColumnRange {
+                        from: Column::new(rect.left),
+                        to: Column::new(rect.right),
+                    }
+                }
+                _ => cell_or_range(Address::new(rect.left, rect.top), to),
+            };
+
+            Tiling::new(&r, size).expect("a single row or column can always be split off")
+        })
+        .collect()
+}
+
+impl RangeOrCell {
+    /// Split the reference into chunks no larger than `size`.  The chunks exactly cover `self`
+    /// without overlapping and are produced left-to-right, then top-to-bottom.  Each member of a
+    /// `NonContiguous` is chunked in turn.  The chunks are always relative (no `$`s) and a
+    /// chunk of a single cell is a `Cell`.
+    ///
+    /// `ColumnRange`s and `RowRange`s can only be split along their bounded axis - so a
+    /// `ColumnRange` can be split by `ChunkSize::Columns`, but not by `Rows`, `Cells` or `Tiles`.
+    ///
+    /// Returns an `Error::InvalidArgumentError` for a `size` of zero or that would split along
+    /// an unbounded axis.
+    pub fn chunks(&self, size: ChunkSize) -> Result<RangeOrCellChunks> {
+        let mut acc = vec![];
+        tilings(self, size, &mut acc)?;
+
+        Ok(RangeOrCellChunks {
+            current: None,
+            tilings: acc.into_iter(),
+        })
+    }
+
+    /// The reference one row at a time, top-to-bottom: `A2:F3` gives `A2:F2` then `A3:F3`.  Like
+    /// `chunks` these are always relative and a single cell is a `Cell`.
+    ///
+    /// A `RowRange` gives each of its (whole) rows, while a `ColumnRange` has a row for every row
+    /// on the grid - so you'll probably want `rows_within` for those.
+    pub fn rows(&self) -> RangeOrCellChunks {
+        self.slices(ChunkSize::Rows(1), None)
+    }
+
+    /// The reference one row at a time, clipped to a sheet whose bottom-right cell is `bounds`.
+    /// So on a sheet that goes to `Z100`, `A:C` gives `A1:C1` through `A100:C100` and `2:3`
+    /// gives `A2:Z2` and `A3:Z3`.
+    pub fn rows_within(&self, bounds: &Address) -> RangeOrCellChunks {
+        self.slices(ChunkSize::Rows(1), Some(bounds))
+    }
+
+    /// The reference one column at a time, left-to-right: `A2:B9` gives `A2:A9` then `B2:B9`.
+    /// See `rows`.
+    pub fn columns(&self) -> RangeOrCellChunks {
+        self.slices(ChunkSize::Columns(1), None)
+    }
+
+    /// The reference one column at a time, clipped to a sheet whose bottom-right cell is
+    /// `bounds`.  See `rows_within`.
+    pub fn columns_within(&self, bounds: &Address) -> RangeOrCellChunks {
+        self.slices(ChunkSize::Columns(1), Some(bounds))
+    }
+
+    fn slices(&self, size: ChunkSize, bounds: Option<&Address>) -> RangeOrCellChunks {
+        RangeOrCellChunks {
+            current: None,
+            tilings: slice_tilings(self, size, bounds).into_iter(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RangeOrCellChunks;
+    use crate::*;
+    use std::str::FromStr;
+
+    fn chunks_to_strs(s: &str, size: ChunkSize) -> Vec<String> {
+        RangeOrCell::from_str(s)
+            .unwrap()
+            .chunks(size)
+            .unwrap()
+            .map(|r| r.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn chunks_cell() {
+        assert_eq!(chunks_to_strs("B2", ChunkSize::Cells(10)), vec!["B2"]);
+    }
+
+    #[test]
+    fn chunks_rows() {
+        assert_eq!(
+            chunks_to_strs("A1:C5", ChunkSize::Rows(2)),
+            vec!["A1:C2", "A3:C4", "A5:C5"]
+        );
+    }
+
+    #[test]
+    fn chunks_columns() {
+        assert_eq!(
+            chunks_to_strs("A1:C2", ChunkSize::Columns(2)),
+            vec!["A1:B2", "C1:C2"]
+        );
+    }
+
+    #[test]
+    fn chunks_cells_whole_rows() {
+        assert_eq!(
+            chunks_to_strs("A1:C4", ChunkSize::Cells(7)),
+            vec!["A1:C2", "A3:C4"]
+        );
+    }
+
+    #[test]
+    fn chunks_cells_split_rows() {
+        assert_eq!(
+            chunks_to_strs("A1:E2", ChunkSize::Cells(2)),
+            vec!["A1:B1", "C1:D1", "E1", "A2:B2", "C2:D2", "E2"]
+        );
+    }
+
+    #[test]
+    fn chunks_tiles() {
+        assert_eq!(
+            chunks_to_strs(
+                "C3:A1",
+                ChunkSize::Tiles {
+                    height: 2,
+                    width: 2
+                }
+            ),
+            vec!["A1:B2", "C1:C2", "A3:B3", "C3"]
+        );
+    }
+
+    #[test]
+    fn chunks_column_range() {
+        assert_eq!(
+            chunks_to_strs("A:E", ChunkSize::Columns(2)),
+            vec!["A:B", "C:D", "E:E"]
+        );
+        assert!(matches!(
+            RangeOrCell::from_str("A:E")
+                .unwrap()
+                .chunks(ChunkSize::Rows(2)),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+    }
+
+    #[test]
+    fn chunks_row_range() {
+        assert_eq!(
+            chunks_to_strs("1:3", ChunkSize::Rows(2)),
+            vec!["1:2", "3:3"]
+        );
+        assert!(matches!(
+            RangeOrCell::from_str("1:3")
+                .unwrap()
+                .chunks(ChunkSize::Cells(100)),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+    }
+
+    #[test]
+    fn chunks_non_contiguous() {
+        assert_eq!(
+            chunks_to_strs("A1:A3,C:D", ChunkSize::Columns(1)),
+            vec!["A1:A3", "C:C", "D:D"]
+        );
+    }
+
+    #[test]
+    fn chunks_zero() {
+        assert!(matches!(
+            RangeOrCell::from_str("A1:B2")
+                .unwrap()
+                .chunks(ChunkSize::Rows(0)),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+    }
+
+    fn slices_to_strs(chunks: RangeOrCellChunks) -> Vec<String> {
+        chunks.map(|r| r.to_string()).collect()
+    }
+
+    #[test]
+    fn rows() {
+        let r = RangeOrCell::from_str("F3:A2").unwrap();
+
+        assert_eq!(slices_to_strs(r.rows()), vec!["A2:F2", "A3:F3"]);
+        assert_eq!(
+            slices_to_strs(RangeOrCell::from_str("A1:A3").unwrap().rows()),
+            vec!["A1", "A2", "A3"]
+        );
+    }
+
+    #[test]
+    fn rows_unbounded() {
+        assert_eq!(
+            slices_to_strs(RangeOrCell::from_str("2:3").unwrap().rows()),
+            vec!["2:2", "3:3"]
+        );
+        assert_eq!(
+            RangeOrCell::from_str("A:C")
+                .unwrap()
+                .rows()
+                .take(2)
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>(),
+            vec!["A1:C1", "A2:C2"]
+        );
+    }
+
+    #[test]
+    fn rows_within() {
+        let bounds = Address::new(25, 2);
+
+        assert_eq!(
+            slices_to_strs(RangeOrCell::from_str("A:C").unwrap().rows_within(&bounds)),
+            vec!["A1:C1", "A2:C2", "A3:C3"]
+        );
+        assert_eq!(
+            slices_to_strs(RangeOrCell::from_str("2:5").unwrap().rows_within(&bounds)),
+            vec!["A2:Z2", "A3:Z3"]
+        );
+        assert!(
+            slices_to_strs(RangeOrCell::from_str("A5").unwrap().rows_within(&bounds)).is_empty()
+        );
+    }
+
+    #[test]
+    fn columns() {
+        assert_eq!(
+            slices_to_strs(RangeOrCell::from_str("A2:B9").unwrap().columns()),
+            vec!["A2:A9", "B2:B9"]
+        );
+        assert_eq!(
+            slices_to_strs(RangeOrCell::from_str("B:C").unwrap().columns()),
+            vec!["B:B", "C:C"]
+        );
+        assert_eq!(
+            slices_to_strs(
+                RangeOrCell::from_str("1:2")
+                    .unwrap()
+                    .columns_within(&Address::new(1, 99))
+            ),
+            vec!["A1:A2", "B1:B2"]
+        );
+    }
+
+    #[test]
+    fn rows_non_contiguous() {
+        assert_eq!(
+            slices_to_strs(RangeOrCell::from_str("A1:B2,D4").unwrap().rows()),
+            vec!["A1:B1", "A2:B2", "D4"]
+        );
+    }
+
+    #[test]
+    fn chunks_cover_exactly() {
+        let range = RangeOrCell::from_str("B2:H30").unwrap();
+        let mut cells = range
+            .chunks(ChunkSize::Cells(5))
+            .unwrap()
+            .flat_map(|c| c.iter().collect::<Vec<_>>())
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        cells.sort();
+
+        let mut expected = range.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        expected.sort();
+
+        assert_eq!(cells, expected);
+    }
+}
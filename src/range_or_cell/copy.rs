@@ -0,0 +1,143 @@
+//! Copying a reference from one cell to another, the way a formula's references change when
+//! it's copied or filled: relative components move along with it and `$`-absolute ones don't.
+use super::RangeOrCell;
+use crate::{Address, Column, Error, Result, Row};
+
+fn copied_column(column: &Column, columns: isize) -> Option<Column> {
+    if column.absolute {
+        Some(*column)
+    } else {
+        column.offset(columns)
+    }
+}
+
+fn copied_row(row: &Row, rows: isize) -> Option<Row> {
+    if row.absolute {
+        Some(*row)
+    } else {
+        row.offset(rows)
+    }
+}
+
+fn copied_address(address: &Address, rows: isize, columns: isize) -> Option<Address> {
+    Some(Address {
+        column: copied_column(&address.column, columns)?,
+        row: copied_row(&address.row, rows)?,
+    })
+}
+
+impl RangeOrCell {
+    /// The reference as it would be written in a formula in the cell `to`, if it was copied
+    /// there from a formula in the cell `from`.  The relative parts move by the distance between
+    /// the two cells while the `absolute` ones stay put, so copying `A$1:$B2` from `A1` to `C3`
+    /// gives `C$1:$B4`.
+    ///
+    /// Each end of a range (and each member of a `NonContiguous`) is copied on its own, and
+    /// nothing is normalized.
+    ///
+    /// Returns an `Error::OutOfBoundsError` if a relative part would end up above row 1 or to
+    /// the left of column A (where a spreadsheet would show a `#REF!`).
+    pub fn copy_to(&self, from: &Address, to: &Address) -> Result<Self> {
+        from.delta_to(to)
+            .and_then(|(rows, columns)| self.checked_copy(rows, columns))
+            .ok_or_else(|| {
+                Error::out_of_bounds(
+                    self,
+                    format!("Copying from {from} to {to} moves a relative reference off the grid"),
+                )
+            })
+    }
+
+    fn checked_copy(&self, rows: isize, columns: isize) -> Option<Self> {
+        Some(match self {
+            Self::Cell(a) => Self::Cell(copied_address(a, rows, columns)?),
+
+            Self::ColumnRange { from, to } => Self::ColumnRange {
+                from: copied_column(from, columns)?,
+                to: copied_column(to, columns)?,
+            },
+
+            Self::NonContiguous(range_or_cells) => Self::NonContiguous(
+                range_or_cells
+                    .iter()
+                    .map(|r| r.checked_copy(rows, columns))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+
+            Self::Range { from, to } => Self::Range {
+                from: copied_address(from, rows, columns)?,
+                to: copied_address(to, rows, columns)?,
+            },
+
+            Self::RowRange { from, to } => Self::RowRange {
+                from: copied_row(from, rows)?,
+                to: copied_row(to, rows)?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn copy(r: &str, from: (usize, usize), to: (usize, usize)) -> Result<String> {
+        RangeOrCell::from_str(r)
+            .unwrap()
+            .copy_to(&from.into(), &to.into())
+            .map(|r| r.to_string())
+    }
+
+    #[test]
+    fn copy_to_cell() {
+        assert_eq!(copy("A1", (0, 0), (2, 3)).unwrap(), "C4");
+        assert_eq!(copy("$A1", (0, 0), (2, 3)).unwrap(), "$A4");
+        assert_eq!(copy("A$1", (0, 0), (2, 3)).unwrap(), "C$1");
+        assert_eq!(copy("$A$1", (0, 0), (2, 3)).unwrap(), "$A$1");
+    }
+
+    #[test]
+    fn copy_to_fill_down() {
+        // filling `=A$1+$B2` from C2 down to C3 and right to D2
+        assert_eq!(copy("A$1", (2, 1), (2, 2)).unwrap(), "A$1");
+        assert_eq!(copy("$B2", (2, 1), (2, 2)).unwrap(), "$B3");
+        assert_eq!(copy("A$1", (2, 1), (3, 1)).unwrap(), "B$1");
+        assert_eq!(copy("$B2", (2, 1), (3, 1)).unwrap(), "$B2");
+    }
+
+    #[test]
+    fn copy_to_range() {
+        assert_eq!(copy("A$1:$B2", (0, 0), (2, 2)).unwrap(), "C$1:$B4");
+        assert_eq!(copy("C3:B2", (2, 2), (1, 1)).unwrap(), "B2:A1");
+    }
+
+    #[test]
+    fn copy_to_column_and_row_ranges() {
+        assert_eq!(copy("A:$C", (0, 0), (1, 5)).unwrap(), "B:$C");
+        assert_eq!(copy("$1:3", (0, 0), (5, 1)).unwrap(), "$1:4");
+    }
+
+    #[test]
+    fn copy_to_non_contiguous() {
+        assert_eq!(
+            RangeOrCell::from_str("A1,$B$2")
+                .unwrap()
+                .copy_to(&(0, 0).into(), &(1, 1).into())
+                .unwrap(),
+            RangeOrCell::NonContiguous(vec![
+                RangeOrCell::from_str("B2").unwrap(),
+                RangeOrCell::from_str("$B$2").unwrap(),
+            ])
+        );
+    }
+
+    #[test]
+    fn copy_to_off_grid() {
+        assert!(copy("A1", (1, 1), (0, 0)).is_err());
+        assert!(copy("B:B", (0, 0), (0, 5)).is_ok());
+        assert!(copy("B:B", (1, 0), (0, 5)).is_ok());
+        assert!(copy("B:B", (2, 0), (0, 5)).is_err());
+        assert!(copy("$A$1", (1, 1), (0, 0)).is_ok());
+    }
+}
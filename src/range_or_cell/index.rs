@@ -0,0 +1,325 @@
+//! Picking cells, rows and columns out of a reference by their position in it, like a
+//! spreadsheet's `INDEX(reference, row, column, area)`.  Positions are zero-based and always
+//! start from the top-left of the reference, no matter which way around it was written.
+use super::addresses::{cell_count, endpoints};
+use super::{cell_or_range, RangeOrCell};
+use crate::{Address, Column, Error, Index, Result, Row};
+
+impl RangeOrCell {
+    /// The cell at (zero-based) `row` and `column` from the top-left of the reference, like
+    /// `INDEX(reference, row + 1, column + 1)`.  The cell has the same `absolute` flags as
+    /// `from`.  A `NonContiguous` uses its first area (again like `INDEX`), use `area` to pick a
+    /// different one.
+    ///
+    /// Returns an `Error::OutOfBoundsError` if the position is outside of the reference.
+    pub fn cell_at(&self, row: Index, column: Index) -> Result<Address> {
+        self.checked_cell_at(row, column).ok_or_else(|| {
+            Error::out_of_bounds(
+                self,
+                format!("Row {row}, column {column} is outside of the reference"),
+            )
+        })
+    }
+
+    /// The `k`th (zero-based) cell of the reference, going row-by-row from the top-left.  A
+    /// `NonContiguous` counts through each of its areas in order.
+    ///
+    /// Returns an `Error::OutOfBoundsError` if the reference has `k` cells or less.
+    pub fn nth_cell(&self, k: Index) -> Result<Address> {
+        self.checked_nth_cell(k as u128).ok_or_else(|| {
+            Error::out_of_bounds(self, format!("There is no cell {k} in the reference"))
+        })
+    }
+
+    /// The reverse of `nth_cell` - how many cells into the reference `address` is.  For a
+    /// `NonContiguous` that's the first area it's in.
+    ///
+    /// Returns `None` if it's not in the reference (or is further in than fits in an `Index`).
+    pub fn position_of(&self, address: &Address) -> Option<Index> {
+        self.checked_position_of(address)
+            .and_then(|p| Index::try_from(p).ok())
+    }
+
+    /// The (zero-based) `n`th row of the reference, like `INDEX(reference, n + 1, 0)`.  A
+    /// `NonContiguous` uses its first area.  (It's not just `row` because that already creates a
+    /// `RowRange`.)
+    ///
+    /// Returns an `Error::OutOfBoundsError` if the reference doesn't have that many rows.
+    pub fn nth_row(&self, n: Index) -> Result<Self> {
+        self.checked_nth_row(n).ok_or_else(|| {
+            Error::out_of_bounds(self, format!("Row {n} is outside of the reference"))
+        })
+    }
+
+    /// The (zero-based) `n`th column of the reference, like `INDEX(reference, 0, n + 1)`.  A
+    /// `NonContiguous` uses its first area.  (It's not just `column` because that already
+    /// creates a `ColumnRange`.)
+    ///
+    /// Returns an `Error::OutOfBoundsError` if the reference doesn't have that many columns.
+    pub fn nth_column(&self, n: Index) -> Result<Self> {
+        self.checked_nth_column(n).ok_or_else(|| {
+            Error::out_of_bounds(self, format!("Column {n} is outside of the reference"))
+        })
+    }
+
+    /// The (zero-based) `n`th area of a `NonContiguous`.  Anything else only has the one area.
+    ///
+    /// Returns an `Error::OutOfBoundsError` if there aren't that many areas.
+    pub fn area(&self, n: Index) -> Result<Self> {
+        match self {
+            Self::NonContiguous(range_or_cells) => range_or_cells.get(n).cloned(),
+            r => (n == 0).then(|| r.clone()),
+        }
+        .ok_or_else(|| Error::out_of_bounds(self, format!("There is no area {n}")))
+    }
+
+    fn checked_cell_at(&self, row: Index, column: Index) -> Option<Address> {
+        if let Self::NonContiguous(range_or_cells) = self {
+            return range_or_cells.first()?.checked_cell_at(row, column);
+        }
+
+        let rect = self.rects()[0];
+        let x = rect.left.checked_add(column).filter(|&x| x <= rect.right)?;
+        let y = rect.top.checked_add(row).filter(|&y| y <= rect.bottom)?;
+
+        let (from, _) = endpoints(self);
+        Some(Address {
+            column: from.column.with_x(x),
+            row: from.row.with_y(y),
+        })
+    }
+
+    fn checked_nth_cell(&self, mut k: u128) -> Option<Address> {
+        if let Self::NonContiguous(range_or_cells) = self {
+            for r in range_or_cells {
+                let count = cell_count(r);
+                if k < count {
+                    return r.checked_nth_cell(k);
+                }
+                k -= count;
+            }
+            return None;
+        }
+
+        let rect = self.rects()[0];
+        let width = (rect.right - rect.left) as u128 + 1;
+        self.checked_cell_at(
+            Index::try_from(k / width).ok()?,
+            // less than the width, which fits
+            (k % width) as Index,
+        )
+    }
+
+    fn checked_position_of(&self, address: &Address) -> Option<u128> {
+        if let Self::NonContiguous(range_or_cells) = self {
+            let mut offset = 0u128;
+            for r in range_or_cells {
+                if let Some(p) = r.checked_position_of(address) {
+                    return offset.checked_add(p);
+                }
+                offset = offset.saturating_add(cell_count(r));
+            }
+            return None;
+        }
+
+        let rect = self.rects()[0];
+        let (x, y) = (address.column.x, address.row.y);
+        if !(rect.left..=rect.right).contains(&x) || !(rect.top..=rect.bottom).contains(&y) {
+            return None;
+        }
+
+        let width = (rect.right - rect.left) as u128 + 1;
+        ((y - rect.top) as u128)
+            .checked_mul(width)?
+            .checked_add((x - rect.left) as u128)
+    }
+
+    fn checked_nth_row(&self, n: Index) -> Option<Self> {
+        let rect = match self {
+            Self::NonContiguous(range_or_cells) => {
+                return range_or_cells.first()?.checked_nth_row(n)
+            }
+            r => r.rects()[0],
+        };
+        let y = rect.top.checked_add(n).filter(|&y| y <= rect.bottom)?;
+
+        Some(match self {
+            Self::Cell(a) => Self::Cell(*a),
+
+            Self::ColumnRange { from, to } => cell_or_range(
+                Address {
+                    column: *from,
+                    row: Row::new(y),
+                },
+                Address {
+                    column: *to,
+                    row: Row::new(y),
+                },
+            ),
+
+            Self::Range { from, to } => cell_or_range(
+                Address {
+                    row: from.row.with_y(y),
+                    ..*from
+                },
+                Address {
+                    row: to.row.with_y(y),
+                    ..*to
+                },
+            ),
+
+            Self::RowRange { from, .. } => {
+                let row = from.with_y(y);
+                Self::RowRange { from: row, to: row }
+            }
+
+            Self::NonContiguous(_) => unreachable!("handled above"),
+        })
+    }
+
+    fn checked_nth_column(&self, n: Index) -> Option<Self> {
+        let rect = match self {
+            Self::NonContiguous(range_or_cells) => {
+                return range_or_cells.first()?.checked_nth_column(n)
+            }
+            r => r.rects()[0],
+        };
+        let x = rect.left.checked_add(n).filter(|&x| x <= rect.right)?;
+
+        Some(match self {
+            Self::Cell(a) => Self::Cell(*a),
+
+            Self::ColumnRange { from, .. } => {
+                let column = from.with_x(x);
+                Self::ColumnRange {
+                    from: column,
+                    to: column,
+                }
+            }
+
+            Self::Range { from, to } => cell_or_range(
+                Address {
+                    column: from.column.with_x(x),
+                    ..*from
+                },
+                Address {
+                    column: to.column.with_x(x),
+                    ..*to
+                },
+            ),
+
+            Self::RowRange { from, to } => cell_or_range(
+                Address {
+                    column: Column::new(x),
+                    row: *from,
+                },
+                Address {
+                    column: Column::new(x),
+                    row: *to,
+                },
+            ),
+
+            Self::NonContiguous(_) => unreachable!("handled above"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn r(s: &str) -> RangeOrCell {
+        RangeOrCell::from_str(s).unwrap()
+    }
+
+    fn a(s: &str) -> Address {
+        Address::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn cell_at() {
+        assert_eq!(r("B2:F9").cell_at(0, 0).unwrap(), a("B2"));
+        assert_eq!(r("B2:F9").cell_at(7, 4).unwrap(), a("F9"));
+        // from the top-left, however it's written
+        assert_eq!(r("F9:B2").cell_at(1, 2).unwrap(), a("D3"));
+        assert_eq!(r("$B$2:F9").cell_at(1, 1).unwrap(), a("$C$3"));
+        assert_eq!(r("C:D").cell_at(99, 1).unwrap(), a("D100"));
+
+        assert!(r("B2:F9").cell_at(8, 0).is_err());
+        assert!(r("B2:F9").cell_at(0, 5).is_err());
+        assert!(r("B2:F9").cell_at(Index::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn nth_cell() {
+        let range = r("B2:F9");
+
+        assert_eq!(range.nth_cell(0).unwrap(), a("B2"));
+        assert_eq!(range.nth_cell(6).unwrap(), a("C3"));
+        assert_eq!(range.nth_cell(39).unwrap(), a("F9"));
+        assert!(range.nth_cell(40).is_err());
+
+        assert_eq!(r("2:3").nth_cell(5).unwrap(), a("F2"));
+    }
+
+    #[test]
+    fn nth_cell_non_contiguous() {
+        let range = r("A1:B2,D4,A1");
+
+        assert_eq!(range.nth_cell(3).unwrap(), a("B2"));
+        assert_eq!(range.nth_cell(4).unwrap(), a("D4"));
+        assert_eq!(range.nth_cell(5).unwrap(), a("A1"));
+        assert!(range.nth_cell(6).is_err());
+    }
+
+    #[test]
+    fn position_of() {
+        let range = r("B2:F9");
+
+        for k in [0, 6, 39] {
+            assert_eq!(range.position_of(&range.nth_cell(k).unwrap()), Some(k));
+        }
+        assert_eq!(range.position_of(&a("A1")), None);
+        assert_eq!(r("A1:B2,D4").position_of(&a("D4")), Some(4));
+        assert_eq!(r("A1:B2,A1:B2").position_of(&a("B2")), Some(3));
+        // further in than fits in a `usize`
+        assert_eq!(r("1:2").position_of(&a("A2")), None);
+    }
+
+    #[test]
+    fn nth_row() {
+        assert_eq!(r("B2:F9").nth_row(2).unwrap(), r("B4:F4"));
+        assert_eq!(r("F9:B2").nth_row(0).unwrap(), r("F2:B2"));
+        assert_eq!(r("B2:B9").nth_row(1).unwrap(), r("B3"));
+        assert_eq!(r("A:C").nth_row(4).unwrap(), r("A5:C5"));
+        assert_eq!(r("2:4").nth_row(1).unwrap(), r("3:3"));
+        assert!(r("B2:F9").nth_row(8).is_err());
+        assert!(r("2:4").nth_row(3).is_err());
+    }
+
+    #[test]
+    fn nth_column() {
+        assert_eq!(r("B2:F9").nth_column(1).unwrap(), r("C2:C9"));
+        assert_eq!(r("A:C").nth_column(2).unwrap(), r("C:C"));
+        assert_eq!(r("2:4").nth_column(3).unwrap(), r("D2:D4"));
+        assert!(r("A:C").nth_column(3).is_err());
+    }
+
+    #[test]
+    fn area() {
+        let range = r("A1:B2,D4");
+
+        assert_eq!(range.area(1).unwrap(), r("D4"));
+        assert!(range.area(2).is_err());
+        assert_eq!(r("A1").area(0).unwrap(), r("A1"));
+        assert!(r("A1").area(1).is_err());
+
+        // INDEX(reference, row, column, area)
+        assert_eq!(
+            r("A1:B2,D4:E5").area(1).unwrap().cell_at(1, 0).unwrap(),
+            a("D5")
+        );
+        assert_eq!(range.cell_at(1, 1).unwrap(), a("B2"));
+    }
+}
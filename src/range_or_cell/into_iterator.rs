@@ -11,8 +11,28 @@ impl Iterator for RangeOrCellIntoIterator {
     fn next(&mut self) -> Option<Self::Item> {
         self.inner_iter.next()
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner_iter.nth(n)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner_iter.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for RangeOrCellIntoIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner_iter.next_back()
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner_iter.nth_back(n)
+    }
 }
 
+impl ExactSizeIterator for RangeOrCellIntoIterator {}
+
 impl IntoIterator for RangeOrCell {
     type Item = RangeOrCell;
     type IntoIter = RangeOrCellIntoIterator;
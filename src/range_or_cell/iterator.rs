@@ -1,224 +1,401 @@
 use super::RangeOrCell;
-use crate::{Address, Column, Row};
+use crate::{Address, Column, Index, Row, Traversal};
 use std::iter;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum HorizontalDirection {
-    Left,
-    Right,
-}
-
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum VerticalDirection {
-    Down,
-    Up,
-}
-
 /// Each `RangeOrCell` requires a different strategy of iteration, so the underlying iterators
 /// reflect that by having an enum variant for each corresponding iterator.
+///
+/// Other than `NonContiguous` they all work out the `i`th item directly, where `front` is the
+/// position of the next item from the front and `back` is one past the next item from the back.
+/// So they can be iterated from either end and skipped through in constant time.
 #[derive(Debug, Clone)]
 pub enum RangeOrCellIterator {
     /// Just stores and emits a single `Address`
     Cell { address: Option<Address> },
 
-    /// Iterates from one column to another, one-by-one.
+    /// Emits the columns from `from` to `to`, one-by-one.
     ColumnRange {
-        current: Option<Column>,
-        horizontal_direction: HorizontalDirection,
-        end: Column,
+        from: Column,
+        to: Column,
+        front: u128,
+        back: u128,
     },
 
     /// For each of the non-contiguous regions, call their iterator function until it's empty.
-    /// Basically act as an aggregation of iterators.
+    /// Basically act as an aggregation of iterators - one working forwards from the member at
+    /// `front` and one backwards from the member before `back`.  `remaining` is how many items
+    /// are left between the two, kept up to date as it goes.
     NonContiguous {
-        iter: Option<Box<RangeOrCellIterator>>,
         range_or_cells: Vec<RangeOrCell>,
-        i: usize,
+        traversal: Traversal,
+        front: usize,
+        back: usize,
+        front_iter: Option<Box<RangeOrCellIterator>>,
+        back_iter: Option<Box<RangeOrCellIterator>>,
+        remaining: u128,
     },
 
-    /// Go row-by-row, left to right until current matches end
+    /// Emits the cells between `from` and `to` in `traversal` order.
     Range {
-        current: Option<Address>,
-        end: Address,
-        horizontal_direction: HorizontalDirection,
-        start: Address,
-        vertical_direction: VerticalDirection,
+        from: Address,
+        to: Address,
+        traversal: Traversal,
+        front: u128,
+        back: u128,
     },
 
-    /// Iterate one-by-one from the start to end row.
+    /// Emits the rows from `from` to `to`, one-by-one.
     RowRange {
-        current: Option<Row>,
-        end: Row,
-        vertical_direction: VerticalDirection,
+        from: Row,
+        to: Row,
+        front: u128,
+        back: u128,
     },
 }
 
-fn horizontal_direction<C: AsRef<Column>>(a: C, b: C) -> HorizontalDirection {
-    if a.as_ref() < b.as_ref() {
-        HorizontalDirection::Right
+/// How many indexes there are from `a` to `b`, inclusive
+pub(crate) fn span(a: Index, b: Index) -> u128 {
+    a.abs_diff(b) as u128 + 1
+}
+
+/// The index `n` steps from `start` towards `end`
+pub(crate) fn step(start: Index, end: Index, n: u128) -> Index {
+    // `n` is never more than the distance between the two, so this fits in an `Index`
+    let n = n as Index;
+    if start <= end {
+        start + n
+    } else {
+        start - n
+    }
+}
+
+/// Take the position `n` items in from the front of `front..back`
+fn take_front(front: &mut u128, back: u128, n: u128) -> Option<u128> {
+    match front.checked_add(n) {
+        Some(i) if i < back => {
+            *front = i + 1;
+            Some(i)
+        }
+        _ => {
+            *front = back;
+            None
+        }
+    }
+}
+
+/// Take the position `n` items in from the back of `front..back`
+fn take_back(front: u128, back: &mut u128, n: u128) -> Option<u128> {
+    if *back - front > n {
+        *back -= n + 1;
+        Some(*back)
     } else {
-        HorizontalDirection::Left
+        *back = front;
+        None
     }
 }
 
-fn vertical_direction<R: AsRef<Row>>(a: R, b: R) -> VerticalDirection {
-    if a.as_ref() < b.as_ref() {
-        VerticalDirection::Down
+/// The `i`th cell (in `traversal` order) of the range from `from` to `to`.  The cells all have
+/// the same `absolute` flags as `from`.
+fn range_cell(from: &Address, to: &Address, traversal: Traversal, i: u128) -> Address {
+    let (width, height) = (span(from.column.x, to.column.x), span(from.row.y, to.row.y));
+    let i = if traversal.is_reverse() {
+        // like `iter_len`, a range of the whole grid saturates rather than overflowing
+        width.saturating_mul(height) - 1 - i
+    } else {
+        i
+    };
+
+    let inner_len = if traversal.is_column_major() {
+        height
+    } else {
+        width
+    };
+    let (outer, mut inner) = (i / inner_len, i % inner_len);
+    if traversal.is_serpentine() && outer % 2 == 1 {
+        inner = inner_len - 1 - inner;
+    }
+
+    let (columns, rows) = if traversal.is_column_major() {
+        (outer, inner)
     } else {
-        VerticalDirection::Up
+        (inner, outer)
+    };
+
+    Address {
+        column: from
+            .column
+            .with_x(step(from.column.x, to.column.x, columns)),
+        row: from.row.with_y(step(from.row.y, to.row.y, rows)),
     }
 }
 
 impl RangeOrCell {
+    /// Iterate over the reference row-by-row.  See `iter_with` for the other orders.
     pub fn iter(&self) -> RangeOrCellIterator {
+        self.iter_with(Traversal::RowMajor)
+    }
+
+    /// Iterate over the reference in the given `traversal` order.  A `ColumnRange` or
+    /// `RowRange` emits whole columns or rows, so for them it only matters whether the order is
+    /// reversed.  A `NonContiguous` goes through each of its members in `traversal` order (and
+    /// goes through the members themselves backwards if it's reversed).
+    pub fn iter_with(&self, traversal: Traversal) -> RangeOrCellIterator {
+        let reverse = traversal.is_reverse();
+
         match self {
             RangeOrCell::Cell(a) => RangeOrCellIterator::Cell { address: Some(*a) },
 
-            RangeOrCell::ColumnRange { from, to } => RangeOrCellIterator::ColumnRange {
-                current: Some(*from),
-                horizontal_direction: horizontal_direction(from, to),
-                end: *to,
-            },
+            RangeOrCell::ColumnRange { from, to } => {
+                let (from, to) = if reverse { (to, from) } else { (from, to) };
+                RangeOrCellIterator::ColumnRange {
+                    from: *from,
+                    to: *to,
+                    front: 0,
+                    back: self.iter_len(),
+                }
+            }
 
             RangeOrCell::NonContiguous(range_or_cells) => RangeOrCellIterator::NonContiguous {
-                iter: None,
-                range_or_cells: range_or_cells.clone(),
-                i: 0,
+                range_or_cells: if reverse {
+                    range_or_cells.iter().rev().cloned().collect()
+                } else {
+                    range_or_cells.clone()
+                },
+                traversal,
+                front: 0,
+                back: range_or_cells.len(),
+                front_iter: None,
+                back_iter: None,
+                remaining: self.iter_len(),
             },
 
             RangeOrCell::Range { from, to } => RangeOrCellIterator::Range {
-                current: Some(*from),
-                end: *to,
-                horizontal_direction: horizontal_direction(from, to),
-                start: *from,
-                vertical_direction: vertical_direction(from, to),
+                from: *from,
+                to: *to,
+                traversal,
+                front: 0,
+                back: self.iter_len(),
             },
 
-            RangeOrCell::RowRange { from, to } => RangeOrCellIterator::RowRange {
-                current: Some(*from),
-                end: *to,
-                vertical_direction: vertical_direction(from, to),
-            },
+            RangeOrCell::RowRange { from, to } => {
+                let (from, to) = if reverse { (to, from) } else { (from, to) };
+                RangeOrCellIterator::RowRange {
+                    from: *from,
+                    to: *to,
+                    front: 0,
+                    back: self.iter_len(),
+                }
+            }
+        }
+    }
+
+    /// How many items `iter` emits
+    fn iter_len(&self) -> u128 {
+        match self {
+            Self::Cell(_) => 1,
+            Self::ColumnRange { from, to } => span(from.x, to.x),
+            Self::NonContiguous(range_or_cells) => range_or_cells
+                .iter()
+                .fold(0, |n, r| n.saturating_add(r.iter_len())),
+            Self::Range { from, to } => {
+                span(from.column.x, to.column.x).saturating_mul(span(from.row.y, to.row.y))
+            }
+            Self::RowRange { from, to } => span(from.y, to.y),
         }
     }
 }
 
-impl iter::Iterator for RangeOrCellIterator {
-    type Item = RangeOrCell;
+impl RangeOrCellIterator {
+    /// How many items are left (which can be more than fit in a `usize`)
+    pub(crate) fn remaining(&self) -> u128 {
+        match self {
+            Self::Cell { address } => address.is_some().into(),
 
-    fn next(&mut self) -> Option<Self::Item> {
+            Self::ColumnRange { front, back, .. }
+            | Self::Range { front, back, .. }
+            | Self::RowRange { front, back, .. } => back - front,
+
+            Self::NonContiguous { remaining, .. } => *remaining,
+        }
+    }
+
+    /// Account for `n` items being skipped and then one more taken, which is all of them if
+    /// there aren't that many left.  Only a `NonContiguous` keeps count - the others work it
+    /// out from their positions.
+    fn skip_remaining(&mut self, n: u128) {
+        if let Self::NonContiguous { remaining, .. } = self {
+            *remaining = remaining.saturating_sub(n.saturating_add(1));
+        }
+    }
+
+    /// The `i`th item (from the start) of the original iterator
+    fn item(&self, i: u128) -> RangeOrCell {
         match self {
-            Self::Cell { ref mut address } => {
-                let a = (*address)?;
-                *address = None;
-                Some(a.into())
+            Self::ColumnRange { from, to, .. } => from.with_x(step(from.x, to.x, i)).into(),
+            Self::Range {
+                from,
+                to,
+                traversal,
+                ..
+            } => range_cell(from, to, *traversal, i).into(),
+            Self::RowRange { from, to, .. } => from.with_y(step(from.y, to.y, i)).into(),
+            Self::Cell { .. } | Self::NonContiguous { .. } => {
+                unreachable!("only called for the positional iterators")
             }
+        }
+    }
 
-            Self::ColumnRange {
-                ref mut current,
-                end,
-                horizontal_direction,
-            } => {
-                let c = (*current)?;
-
-                *current = if c == *end {
-                    None
-                } else if *horizontal_direction == HorizontalDirection::Right {
-                    Some(c.shift_right(1))
-                } else {
-                    Some(c.shift_left(1))
-                };
+    /// Skip `n` items from the front and return the one after that
+    fn nth_front(&mut self, mut n: u128) -> Option<RangeOrCell> {
+        self.skip_remaining(n);
+
+        match self {
+            Self::Cell { ref mut address } => {
+                let a = address.take()?;
+                (n == 0).then(|| a.into())
+            }
 
-                Some(c.into())
+            Self::ColumnRange { front, back, .. }
+            | Self::Range { front, back, .. }
+            | Self::RowRange { front, back, .. } => {
+                let i = take_front(front, *back, n)?;
+                Some(self.item(i))
             }
 
             Self::NonContiguous {
-                ref mut i,
-                ref mut iter,
                 range_or_cells,
-            } => {
+                traversal,
+                ref mut front,
+                back,
+                ref mut front_iter,
+                ref mut back_iter,
+                ..
+            } => loop {
                 // if we have an active iter, just use it until it runs out
-                if let Some(i) = iter {
-                    let n = i.next();
-                    if n.is_some() {
-                        return n;
+                if let Some(i) = front_iter {
+                    let remaining = i.remaining();
+                    if n < remaining {
+                        return i.nth_front(n);
+                    }
+                    n -= remaining;
+                }
+                *front_iter = None;
+
+                if *front < *back {
+                    // skip over whole members without even making an iterator for them
+                    let r = &range_or_cells[*front];
+                    *front += 1;
+                    let len = r.iter_len();
+                    if n >= len {
+                        n -= len;
+                    } else {
+                        *front_iter = Some(Box::new(r.iter_with(*traversal)));
                     }
+                } else {
+                    // all that's left is whatever the back is partway through
+                    return back_iter.as_mut()?.nth_front(n);
                 }
+            },
+        }
+    }
 
-                if let Some(r) = range_or_cells.get(*i) {
-                    let mut r_iter = r.iter();
-                    let next_value = r_iter.next();
+    /// Skip `n` items from the back and return the one before that
+    fn nth_back_from(&mut self, mut n: u128) -> Option<RangeOrCell> {
+        self.skip_remaining(n);
 
-                    // we have an iterator - save it and increment `i` to signify we'll move onto
-                    // the next `RangeOrCell`
-                    *i += 1;
-                    *iter = Some(Box::new(r_iter));
+        match self {
+            Self::Cell { .. } => self.nth_front(n),
 
-                    next_value
-                } else {
-                    None
-                }
+            Self::ColumnRange { front, back, .. }
+            | Self::Range { front, back, .. }
+            | Self::RowRange { front, back, .. } => {
+                let i = take_back(*front, back, n)?;
+                Some(self.item(i))
             }
 
-            Self::Range {
-                ref mut current,
-                horizontal_direction,
-                start,
-                end,
-                vertical_direction,
-            } => {
-                let c = (*current)?;
-                let current_col: &Column = c.as_ref();
-
-                // figure out the next value by traversing left/right row-wise then up/down
-                *current =
-                    // if we're past `end` (depending on which direction) then we're done
-                    if c == *end {
-                        None
-                    // are we hitting the bounds of the range?
-                    } else if current_col == end.as_ref() {
-                        // then we need to shift up or down *and* reset our position on the next row
-                        Some(if *vertical_direction == VerticalDirection::Up {
-                            c.shift_up(1)
-                        } else {
-                            c.shift_down(1)
-                        }.with_x(start.column.x))
+            Self::NonContiguous {
+                range_or_cells,
+                traversal,
+                front,
+                ref mut back,
+                ref mut front_iter,
+                ref mut back_iter,
+                ..
+            } => loop {
+                if let Some(i) = back_iter {
+                    let remaining = i.remaining();
+                    if n < remaining {
+                        return i.nth_back_from(n);
+                    }
+                    n -= remaining;
+                }
+                *back_iter = None;
+
+                if *front < *back {
+                    *back -= 1;
+                    let r = &range_or_cells[*back];
+                    let len = r.iter_len();
+                    if n >= len {
+                        n -= len;
                     } else {
-                        // we're in-bounds so we just need to shift left or right
-                        Some(if *horizontal_direction == HorizontalDirection::Left {
-                            c.shift_left(1)
-                        } else {
-                            c.shift_right(1)
-                        })
-                    };
-
-                Some(c.into())
-            }
-
-            Self::RowRange {
-                ref mut current,
-                end,
-                vertical_direction,
-            } => {
-                let c = (*current)?;
-
-                *current = if c == *end {
-                    None
-                } else if *vertical_direction == VerticalDirection::Down {
-                    Some(c.shift_down(1))
+                        *back_iter = Some(Box::new(r.iter_with(*traversal)));
+                    }
                 } else {
-                    Some(c.shift_up(1))
-                };
+                    return front_iter.as_mut()?.nth_back_from(n);
+                }
+            },
+        }
+    }
+}
 
-                Some(c.into())
-            }
+impl iter::Iterator for RangeOrCellIterator {
+    type Item = RangeOrCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nth_front(0)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.nth_front(n as u128)
+    }
+
+    /// Exact, unless there are more than `usize::MAX` items left
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining()) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
         }
     }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl iter::DoubleEndedIterator for RangeOrCellIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.nth_back_from(0)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.nth_back_from(n as u128)
+    }
 }
 
+/// Every reference has a finite number of cells (or columns or rows).  But a range with more
+/// than `usize::MAX` cells will panic when you ask for its `len`.
+impl iter::ExactSizeIterator for RangeOrCellIterator {}
+
+impl iter::FusedIterator for RangeOrCellIterator {}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
+    use std::str::FromStr;
 
     fn range_to_strs(range: RangeOrCell) -> Vec<String> {
         range.iter().map(|r| r.to_string()).collect()
@@ -303,6 +480,237 @@ mod tests {
         );
     }
 
+    #[test]
+    fn iter_range_keeps_absolute() {
+        assert_eq!(
+            range_to_strs(RangeOrCell::from_str("$A$1:B2").unwrap()),
+            vec!["$A$1", "$B$1", "$A$2", "$B$2"]
+        );
+    }
+
+    fn traverse(r: &str, traversal: Traversal) -> Vec<String> {
+        RangeOrCell::from_str(r)
+            .unwrap()
+            .iter_with(traversal)
+            .map(|r| r.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn iter_with_column_major() {
+        assert_eq!(
+            traverse("A1:B3", Traversal::ColumnMajor),
+            vec!["A1", "A2", "A3", "B1", "B2", "B3"]
+        );
+        assert_eq!(
+            traverse("B3:A1", Traversal::ColumnMajor),
+            vec!["B3", "B2", "B1", "A3", "A2", "A1"]
+        );
+    }
+
+    #[test]
+    fn iter_with_reverse() {
+        assert_eq!(
+            traverse("A1:B3", Traversal::ReverseRowMajor),
+            vec!["B3", "A3", "B2", "A2", "B1", "A1"]
+        );
+        assert_eq!(
+            traverse("A1:B3", Traversal::ReverseColumnMajor),
+            vec!["B3", "B2", "B1", "A3", "A2", "A1"]
+        );
+        assert_eq!(
+            traverse("B3:A1", Traversal::ReverseRowMajor),
+            vec!["A1", "B1", "A2", "B2", "A3", "B3"]
+        );
+    }
+
+    #[test]
+    fn iter_with_serpentine() {
+        assert_eq!(
+            traverse("A1:C3", Traversal::RowSerpentine),
+            vec!["A1", "B1", "C1", "C2", "B2", "A2", "A3", "B3", "C3"]
+        );
+        assert_eq!(
+            traverse("A1:B3", Traversal::ColumnSerpentine),
+            vec!["A1", "A2", "A3", "B3", "B2", "B1"]
+        );
+    }
+
+    #[test]
+    fn iter_with_column_and_row_ranges() {
+        assert_eq!(
+            traverse("A:C", Traversal::ColumnMajor),
+            vec!["A:A", "B:B", "C:C"]
+        );
+        assert_eq!(
+            traverse("A:C", Traversal::ReverseRowMajor),
+            vec!["C:C", "B:B", "A:A"]
+        );
+        assert_eq!(
+            traverse("3:1", Traversal::ReverseColumnMajor),
+            vec!["1:1", "2:2", "3:3"]
+        );
+        assert_eq!(traverse("B2", Traversal::ReverseRowMajor), vec!["B2"]);
+    }
+
+    #[test]
+    fn iter_with_non_contiguous() {
+        let r = RangeOrCell::NonContiguous(vec![
+            RangeOrCell::from_str("A1:B2").unwrap(),
+            RangeOrCell::from_str("D4").unwrap(),
+        ]);
+        let traverse = |traversal| {
+            r.iter_with(traversal)
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            traverse(Traversal::ColumnMajor),
+            vec!["A1", "A2", "B1", "B2", "D4"]
+        );
+        assert_eq!(
+            traverse(Traversal::ReverseRowMajor),
+            vec!["D4", "B2", "A2", "B1", "A1"]
+        );
+    }
+
+    #[test]
+    fn iter_rev() {
+        let r = RangeOrCell::from_str("A1:C2").unwrap();
+
+        assert_eq!(
+            r.iter().rev().map(|r| r.to_string()).collect::<Vec<_>>(),
+            vec!["C2", "B2", "A2", "C1", "B1", "A1"]
+        );
+        assert_eq!(
+            r.iter()
+                .rev()
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .collect::<Vec<_>>(),
+            r.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn iter_both_ends() {
+        let mut i = RangeOrCell::from_str("A:D").unwrap().iter();
+
+        assert_eq!(i.next().unwrap().to_string(), "A:A");
+        assert_eq!(i.next_back().unwrap().to_string(), "D:D");
+        assert_eq!(i.len(), 2);
+        assert_eq!(i.next_back().unwrap().to_string(), "C:C");
+        assert_eq!(i.next().unwrap().to_string(), "B:B");
+        assert_eq!(i.next(), None);
+        assert_eq!(i.next_back(), None);
+    }
+
+    #[test]
+    fn iter_len() {
+        assert_eq!(RangeOrCell::from_str("B2").unwrap().iter().len(), 1);
+        assert_eq!(
+            RangeOrCell::from_str("A1:Z100000").unwrap().iter().len(),
+            2_600_000
+        );
+        assert_eq!(RangeOrCell::from_str("3:1").unwrap().iter().len(), 3);
+        assert_eq!(
+            RangeOrCell::from_str("A1:B2,C:C,D4").unwrap().iter().len(),
+            6
+        );
+
+        let mut i = RangeOrCell::from_str("A1:C3").unwrap().iter();
+        i.next();
+        assert_eq!(i.size_hint(), (8, Some(8)));
+    }
+
+    #[test]
+    fn iter_whole_grid_reversed() {
+        let whole = RangeOrCell::Range {
+            from: Address::new(0, 0),
+            to: Address::new(usize::MAX, usize::MAX),
+        };
+
+        // `2^64 * 2^64` cells don't fit in a `u128`, but reversing shouldn't overflow
+        assert!(whole.iter_with(Traversal::ReverseRowMajor).nth(1).is_some());
+        assert!(whole
+            .iter_with(Traversal::ReverseColumnMajor)
+            .next()
+            .is_some());
+    }
+
+    #[test]
+    fn iter_len_non_contiguous_as_it_goes() {
+        let r = RangeOrCell::NonContiguous(vec![
+            RangeOrCell::from_str("A1:B2").unwrap(),
+            RangeOrCell::from_str("C3,D4:E5").unwrap(),
+            RangeOrCell::from_str("F6").unwrap(),
+        ]);
+        let mut i = r.iter();
+        assert_eq!(i.len(), 10);
+
+        i.next();
+        i.next_back();
+        assert_eq!(i.len(), 8);
+        assert_eq!(i.nth(4).unwrap().to_string(), "D4");
+        assert_eq!(i.len(), 3);
+        assert_eq!(i.nth_back(1).unwrap().to_string(), "D5");
+        assert_eq!(i.len(), 1);
+        assert_eq!(i.nth(3), None);
+        assert_eq!(i.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn iter_nth() {
+        let r = RangeOrCell::from_str("A1:Z100000").unwrap();
+
+        assert_eq!(r.iter().nth(26).unwrap().to_string(), "A2");
+        assert_eq!(r.iter().last().unwrap().to_string(), "Z100000");
+        assert_eq!(r.iter().rev().nth(26).unwrap().to_string(), "Z99999");
+        assert_eq!(r.iter().nth(2_600_000), None);
+
+        let mut i = r.iter_with(Traversal::ColumnMajor);
+        assert_eq!(i.nth(100_000).unwrap().to_string(), "B1");
+        assert_eq!(i.next().unwrap().to_string(), "B2");
+    }
+
+    #[test]
+    fn iter_nth_cell() {
+        let mut i = RangeOrCell::from_str("B2").unwrap().iter();
+        assert_eq!(i.nth(1), None);
+        assert_eq!(i.next(), None);
+    }
+
+    #[test]
+    fn iter_non_contiguous_both_ends() {
+        let r = RangeOrCell::from_str("A1:B2,C:C,D4").unwrap();
+        let all = r.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(all, vec!["A1", "B1", "A2", "B2", "C:C", "D4"]);
+        assert_eq!(
+            r.iter().rev().map(|r| r.to_string()).collect::<Vec<_>>(),
+            all.iter().rev().cloned().collect::<Vec<_>>()
+        );
+
+        // every way of nth-ing into it agrees with the collected version
+        for n in 0..all.len() + 1 {
+            assert_eq!(r.iter().nth(n).map(|r| r.to_string()), all.get(n).cloned());
+            assert_eq!(
+                r.iter().nth_back(n).map(|r| r.to_string()),
+                all.iter().rev().nth(n).cloned()
+            );
+        }
+
+        // meeting in the middle of a member
+        let mut i = r.iter();
+        assert_eq!(i.nth(1).unwrap().to_string(), "B1");
+        assert_eq!(i.nth_back(3).unwrap().to_string(), "A2");
+        assert_eq!(i.len(), 0);
+        assert_eq!(i.next(), None);
+        assert_eq!(i.next_back(), None);
+    }
+
     #[test]
     fn iter_row_range() {
         let range = RangeOrCell::RowRange {
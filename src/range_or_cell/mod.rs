@@ -4,11 +4,26 @@
 //!
 use crate::{Address, Column, Index, Row, A1};
 
+mod absolute;
+pub mod addresses;
+pub mod border;
+pub mod chunks;
+mod copy;
 mod display;
 mod from_str;
+mod index;
 mod into;
 mod into_iterator;
 pub mod iterator;
+mod offset;
+mod ord;
+#[cfg(feature = "rayon")]
+pub mod par_iter;
+mod partial_ord;
+mod structural;
+mod transpose;
+pub mod unique;
+pub mod windows;
 
 #[cfg_attr(
     feature = "rkyv",
@@ -26,7 +41,7 @@ pub mod iterator;
     ))
 )]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum RangeOrCell {
     /// Just a single cell
     Cell(Address),
@@ -55,6 +70,15 @@ pub enum RangeOrCell {
     RowRange { from: Row, to: Row },
 }
 
+/// A `Range` between the two corners, collapsing down to a `Cell` if they're the same
+pub(crate) fn cell_or_range(from: Address, to: Address) -> RangeOrCell {
+    if from.column.x == to.column.x && from.row.y == to.row.y {
+        RangeOrCell::Cell(from)
+    } else {
+        RangeOrCell::Range { from, to }
+    }
+}
+
 impl RangeOrCell {
     /// Create a `RangeOrCell::ColumnRange` at the given `x` index
     pub fn column<C: Into<Column>>(x: C) -> Self {
@@ -92,113 +116,33 @@ impl RangeOrCell {
         }
     }
 
-    /// This function has a lot going on because we need to handle every combination of every
-    /// `RangeOrCell` containing every other combination of a `RangeOrCell`.  The rules are
-    /// nuanced but I think intuitive if you think through how it would look on a grid.
+    /// Is every cell of `other` also a cell of `self`?  This is plain set containment over the
+    /// cells each reference covers, so it works the same for every pairing of variants:
+    ///
+    /// * A `ColumnRange` covers every row of its columns and a `RowRange` covers every column
+    ///   of its rows, so a `Range` can only contain them if it spans the whole grid in that
+    ///   direction.
+    /// * A `NonContiguous` covers the union of its members, so it can contain something that
+    ///   straddles two of its members (`A1:A2,B1:B2` contains `A1:B2`).  And it's contained by
+    ///   `self` when all of its members are.
+    /// * `absolute` flags and the direction of a range (`B2:A1` vs `A1:B2`) don't matter.
+    /// * An empty `NonContiguous` has no cells, so it's contained by everything.
     pub fn contains(&self, other: &Self) -> bool {
-        match self {
-            Self::Cell(a) => {
-                match other {
-                    // a cell can only contain itself, nothing larger (i.e. a range)
-                    Self::Cell(other_a) => a == other_a,
-
-                    // they're asking if `self` (a `Cell(Address)`) "contains" a list of ranges...
-                    // the only way that would happen is if it was a list of the same point.  but
-                    // for thoroughness we'll try:
-                    Self::NonContiguous(o) => o.iter().all(|oa| oa.contains(self)),
-
-                    // anything else is larger than a cell and wouldn't be able to be contained by
-                    // it
-                    _ => false,
-                }
-            }
-
-            Self::ColumnRange { from, to } => {
-                match other {
-                    // it needs to be completely contained by the `self`s column range
-                    Self::ColumnRange {
-                        from: other_from,
-                        to: other_to,
-                    } => other_from.is_between(from, to) && other_to.is_between(from, to),
-
-                    // the cell just needs to be between the two columns
-                    Self::Cell(a) => {
-                        let a_col: &Column = a.as_ref();
-                        a_col.is_between(from, to)
-                    }
-
-                    // our column range has to contain all of `other`s points
-                    Self::NonContiguous(r) => r.iter().all(|oa| oa.contains(self)),
-
-                    Self::Range {
-                        from: other_from,
-                        to: other_to,
-                    } => {
-                        let other_from_col: &Column = other_from.as_ref();
-                        let other_to_col: &Column = other_to.as_ref();
-
-                        other_from_col.is_between(from, to) && other_to_col.is_between(from, to)
-                    }
-
-                    // a row range can never be contained inside a column range
-                    Self::RowRange { .. } => false,
-                }
-            }
-
-            // we just need to know if any of the ranges in our NonContiguous contain it
-            Self::NonContiguous(range_or_cells) => range_or_cells.iter().any(|r| r.contains(other)),
-
-            Self::RowRange { from, to } => {
-                match other {
-                    // a column range would never be able to be contained in a row range
-                    Self::ColumnRange { .. } => false,
-
-                    // the cell just needs to be between the two rows
-                    Self::Cell(a) => {
-                        let a_row: &Row = a.as_ref();
-                        a_row.is_between(from, to)
-                    }
-
-                    Self::RowRange {
-                        from: other_from,
-                        to: other_to,
-                    } => other_from.is_between(from, to) && other_to.is_between(from, to),
-
-                    // our row range has to contain all of `other`s points
-                    Self::NonContiguous(r) => r.iter().all(|oa| oa.contains(self)),
-
-                    Self::Range {
-                        from: other_from,
-                        to: other_to,
-                    } => {
-                        let other_from_row: &Row = other_from.as_ref();
-                        let other_to_row: &Row = other_to.as_ref();
-
-                        other_from_row.is_between(from, to) && other_to_row.is_between(from, to)
-                    }
-                }
-            }
-
-            Self::Range { from, to } => {
-                match other {
-                    // a bounded range (`Range`) can't contain an unbounded range
-                    Self::ColumnRange { .. } => false,
-
-                    Self::Cell(a) => a.is_between(from, to),
-
-                    // a bounded range (`Range`) can't contain an unbounded range
-                    Self::RowRange { .. } => false,
+        let rects = self.rects();
+        other.rects().iter().all(|r| r.is_covered_by(&rects))
+    }
 
-                    // our row range has to contain all of `other`s points
-                    Self::NonContiguous(r) => r.iter().all(|oa| oa.contains(self)),
+    /// Do `self` and `other` cover exactly the same cells?  This ignores the `absolute` flags and
+    /// how the reference is written, so `A1:B2`, `$B$2:A1` and `A1:A2,B1:B2` are all the same
+    /// area.  Use `is_identical` (or `==`) to compare them exactly.
+    pub fn is_same_area(&self, other: &Self) -> bool {
+        self.covers_same_cells(other)
+    }
 
-                    Self::Range {
-                        from: other_from,
-                        to: other_to,
-                    } => other_from.is_between(from, to) && other_to.is_between(from, to),
-                }
-            }
-        }
+    /// Are `self` and `other` written exactly the same way, including their `absolute` flags?
+    /// This is the same as `==`.
+    pub fn is_identical(&self, other: &Self) -> bool {
+        self == other
     }
 
     pub fn shift_down(self, rows: usize) -> Self {
@@ -367,6 +311,7 @@ impl RangeOrCell {
 #[cfg(test)]
 mod tests {
     use crate::*;
+    use std::str::FromStr;
 
     #[test]
     fn contains_cell() {
@@ -432,6 +377,59 @@ mod tests {
         assert!(!range.contains(&RangeOrCell::Cell((100, 100).into())));
     }
 
+    #[test]
+    fn contains_non_contiguous() {
+        let a1_c1 = RangeOrCell::from_str("A1,B1,C1").unwrap();
+
+        assert!(a1_c1.contains(&RangeOrCell::from_str("A1:C1").unwrap()));
+        assert!(a1_c1.contains(&RangeOrCell::from_str("C1,A1").unwrap()));
+        assert!(!a1_c1.contains(&RangeOrCell::from_str("A1:D1").unwrap()));
+        assert!(RangeOrCell::from_str("A1:C1").unwrap().contains(&a1_c1));
+
+        // there's nothing in an empty `NonContiguous`
+        assert!(RangeOrCell::Cell((0, 0).into()).contains(&RangeOrCell::NonContiguous(vec![])));
+        assert!(!RangeOrCell::NonContiguous(vec![]).contains(&RangeOrCell::Cell((0, 0).into())));
+    }
+
+    #[test]
+    fn contains_non_contiguous_by_cell() {
+        // a cell only contains a `NonContiguous` made up of just that cell
+        let b2 = RangeOrCell::Cell((1, 1).into());
+
+        assert!(b2.contains(&RangeOrCell::from_str("B2,$B$2").unwrap()));
+        assert!(!b2.contains(&RangeOrCell::from_str("B2,A1:C3").unwrap()));
+    }
+
+    #[test]
+    fn contains_non_contiguous_by_column_range() {
+        let a_c = RangeOrCell::column_range(0, 2);
+
+        assert!(a_c.contains(&RangeOrCell::from_str("A:A,C:C").unwrap()));
+        assert!(a_c.contains(&RangeOrCell::from_str("A1,B5:C10").unwrap()));
+        assert!(!a_c.contains(&RangeOrCell::from_str("A:A,D:D").unwrap()));
+    }
+
+    #[test]
+    fn contains_row_range_by_full_width_range() {
+        let full_width = RangeOrCell::Range {
+            from: (0, 0).into(),
+            to: (Index::MAX, 5).into(),
+        };
+
+        assert!(full_width.contains(&RangeOrCell::row_range(1, 2)));
+        assert!(!full_width.contains(&RangeOrCell::row_range(1, 6)));
+    }
+
+    #[test]
+    fn contains_absolute_and_reversed() {
+        assert!(RangeOrCell::from_str("C3:A1")
+            .unwrap()
+            .contains(&RangeOrCell::from_str("$B$2").unwrap()));
+        assert!(RangeOrCell::from_str("F1:A6")
+            .unwrap()
+            .contains(&RangeOrCell::from_str("B2").unwrap()));
+    }
+
     #[test]
     fn contains_row_range() {
         let row_range = RangeOrCell::RowRange {
@@ -458,6 +456,28 @@ mod tests {
         assert!(!row_range.contains(&RangeOrCell::Cell((100, 100).into())));
     }
 
+    #[test]
+    fn is_same_area() {
+        let a1_b2 = RangeOrCell::from_str("A1:B2").unwrap();
+
+        assert!(a1_b2.is_same_area(&RangeOrCell::from_str("$B$2:A1").unwrap()));
+        assert!(a1_b2.is_same_area(&RangeOrCell::from_str("A1:A2,B1:B2").unwrap()));
+        assert!(RangeOrCell::from_str("A1")
+            .unwrap()
+            .is_same_area(&RangeOrCell::from_str("$A1:A$1").unwrap()));
+        assert!(!a1_b2.is_same_area(&RangeOrCell::from_str("A1:B3").unwrap()));
+        assert!(!a1_b2.is_same_area(&RangeOrCell::from_str("A:B").unwrap()));
+    }
+
+    #[test]
+    fn is_identical() {
+        let a1_b2 = RangeOrCell::from_str("A1:B2").unwrap();
+
+        assert!(a1_b2.is_identical(&RangeOrCell::from_str("A1:B2").unwrap()));
+        assert!(!a1_b2.is_identical(&RangeOrCell::from_str("$A$1:B2").unwrap()));
+        assert!(!a1_b2.is_identical(&RangeOrCell::from_str("B2:A1").unwrap()));
+    }
+
     #[test]
     fn shift_down_cell() {
         assert_eq!(
@@ -0,0 +1,480 @@
+//! Excel's `OFFSET()` and VBA's `Range.Offset`/`Range.Resize` style operations.  Unlike the
+//! `shift_*` functions these never clamp - if the result would leave the grid you get an error.
+use super::{cell_or_range, RangeOrCell};
+use crate::{Address, Column, Direction, Error, Index, Result, Row};
+use std::cmp;
+
+fn sized_column(start: &Column, end: &Column, width: Option<Index>) -> Option<Column> {
+    match width {
+        Some(0) => None,
+        Some(w) => Some(end.with_x(start.x.checked_add(w - 1)?)),
+        None => Some(*end),
+    }
+}
+
+fn sized_row(start: &Row, end: &Row, height: Option<Index>) -> Option<Row> {
+    match height {
+        Some(0) => None,
+        Some(h) => Some(end.with_y(start.y.checked_add(h - 1)?)),
+        None => Some(*end),
+    }
+}
+
+impl RangeOrCell {
+    /// Move the reference by `rows` and `columns` (negative values move up and left), like
+    /// Excel's `OFFSET(ref, rows, cols)`.  The `absolute` flags are kept as they are.
+    ///
+    /// A `ColumnRange` spans every row, so moving it vertically leaves it as-is (and likewise
+    /// for a `RowRange` moved horizontally).  A `NonContiguous` moves each of its members.
+    ///
+    /// Returns an `Error::OutOfBoundsError` if any part would move off of the grid.
+    pub fn offset(&self, rows: isize, columns: isize) -> Result<Self> {
+        self.checked_offset(rows, columns).ok_or_else(|| {
+            Error::out_of_bounds(
+                self,
+                format!("Offsetting by {rows} rows and {columns} columns leaves the grid"),
+            )
+        })
+    }
+
+    fn checked_offset(&self, rows: isize, columns: isize) -> Option<Self> {
+        Some(match self {
+            Self::Cell(a) => Self::Cell(a.offset(rows, columns)?),
+
+            Self::ColumnRange { from, to } => Self::ColumnRange {
+                from: from.offset(columns)?,
+                to: to.offset(columns)?,
+            },
+
+            Self::NonContiguous(range_or_cells) => Self::NonContiguous(
+                range_or_cells
+                    .iter()
+                    .map(|r| r.checked_offset(rows, columns))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+
+            Self::Range { from, to } => Self::Range {
+                from: from.offset(rows, columns)?,
+                to: to.offset(rows, columns)?,
+            },
+
+            Self::RowRange { from, to } => Self::RowRange {
+                from: from.offset(rows)?,
+                to: to.offset(rows)?,
+            },
+        })
+    }
+
+    /// Change the size of the reference while keeping its top-left corner where it is, like
+    /// VBA's `Range.Resize(rows, columns)`.  A `None` `height` or `width` keeps the current
+    /// size along that axis.  The result is normalized so that `from` is the top-left corner
+    /// and resizing down to a single cell gives you a `Cell`.
+    ///
+    /// Giving a `ColumnRange` a `height` turns it into a `Range` starting at row 1 (and a
+    /// `RowRange` given a `width` becomes a `Range` starting at column A).  A `NonContiguous`
+    /// resizes each of its members.
+    ///
+    /// Returns an `Error::InvalidArgumentError` if `height` or `width` are zero and an
+    /// `Error::OutOfBoundsError` if it would grow off of the grid.
+    pub fn resize(&self, height: Option<Index>, width: Option<Index>) -> Result<Self> {
+        if height == Some(0) || width == Some(0) {
+            return Err(Error::invalid_argument(
+                self,
+                format!("Can not resize to a height of {height:?} and width of {width:?}"),
+            ));
+        }
+
+        self.checked_resize(height, width).ok_or_else(|| {
+            Error::out_of_bounds(
+                self,
+                format!("Unable to resize to a height of {height:?} and width of {width:?}"),
+            )
+        })
+    }
+
+    fn checked_resize(&self, height: Option<Index>, width: Option<Index>) -> Option<Self> {
+        Some(match self {
+            Self::Cell(a) => cell_or_range(
+                *a,
+                Address {
+                    column: sized_column(&a.column, &a.column, width)?,
+                    row: sized_row(&a.row, &a.row, height)?,
+                },
+            ),
+
+            Self::ColumnRange { from, to } => {
+                let left = cmp::min(*from, *to);
+                let right = sized_column(&left, &cmp::max(*from, *to), width)?;
+
+                if height.is_some() {
+                    let top = Row::new(0);
+                    cell_or_range(
+                        Address {
+                            column: left,
+                            row: top,
+                        },
+                        Address {
+                            column: right,
+                            row: sized_row(&top, &top, height)?,
+                        },
+                    )
+                } else {
+                    Self::ColumnRange {
+                        from: left,
+                        to: right,
+                    }
+                }
+            }
+
+            Self::NonContiguous(range_or_cells) => Self::NonContiguous(
+                range_or_cells
+                    .iter()
+                    .map(|r| r.checked_resize(height, width))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+
+            Self::Range { from, to } => {
+                let (top_left, bottom_right) = Address::corners(from, to);
+
+                cell_or_range(
+                    top_left,
+                    Address {
+                        column: sized_column(&top_left.column, &bottom_right.column, width)?,
+                        row: sized_row(&top_left.row, &bottom_right.row, height)?,
+                    },
+                )
+            }
+
+            Self::RowRange { from, to } => {
+                let top = cmp::min(*from, *to);
+                let bottom = sized_row(&top, &cmp::max(*from, *to), height)?;
+
+                if width.is_some() {
+                    let left = Column::new(0);
+                    cell_or_range(
+                        Address {
+                            column: left,
+                            row: top,
+                        },
+                        Address {
+                            column: sized_column(&left, &left, width)?,
+                            row: bottom,
+                        },
+                    )
+                } else {
+                    Self::RowRange {
+                        from: top,
+                        to: bottom,
+                    }
+                }
+            }
+        })
+    }
+
+    /// Grow the reference so that it also covers `address`, like shift-clicking a cell.  A
+    /// `ColumnRange` only grows horizontally and a `RowRange` only grows vertically.  For a
+    /// `NonContiguous` the last member is expanded, which is the area that's active in a
+    /// multi-selection.
+    pub fn expand_to(&self, address: Address) -> Self {
+        match self {
+            Self::Cell(a) => {
+                let (top_left, bottom_right) = Address::corners(a, &address);
+                cell_or_range(top_left, bottom_right)
+            }
+
+            Self::ColumnRange { from, to } => Self::ColumnRange {
+                from: cmp::min(cmp::min(*from, *to), address.column),
+                to: cmp::max(cmp::max(*from, *to), address.column),
+            },
+
+            Self::NonContiguous(range_or_cells) => {
+                let mut range_or_cells = range_or_cells.clone();
+                if let Some(last) = range_or_cells.pop() {
+                    range_or_cells.push(last.expand_to(address));
+                }
+                Self::NonContiguous(range_or_cells)
+            }
+
+            Self::Range { from, to } => {
+                let (top_left, bottom_right) = Address::corners(from, to);
+                let (top_left, _) = Address::corners(&top_left, &address);
+                let (_, bottom_right) = Address::corners(&bottom_right, &address);
+
+                Self::Range {
+                    from: top_left,
+                    to: bottom_right,
+                }
+            }
+
+            Self::RowRange { from, to } => Self::RowRange {
+                from: cmp::min(cmp::min(*from, *to), address.row),
+                to: cmp::max(cmp::max(*from, *to), address.row),
+            },
+        }
+    }
+
+    /// Grow the reference by `n` cells in the given `direction`, keeping the opposite edge where
+    /// it is.  Growing a `ColumnRange` up or down (or a `RowRange` left or right) does nothing
+    /// since it already spans the whole axis.  A `NonContiguous` extends each of its members.
+    ///
+    /// Returns an `Error::OutOfBoundsError` if the reference would grow off of the grid.
+    pub fn extend(&self, direction: Direction, n: Index) -> Result<Self> {
+        self.checked_extend(direction, n).ok_or_else(|| {
+            Error::out_of_bounds(
+                self,
+                format!("Extending {direction:?} by {n} leaves the grid"),
+            )
+        })
+    }
+
+    fn checked_extend(&self, direction: Direction, n: Index) -> Option<Self> {
+        if n == 0 {
+            return Some(self.clone());
+        }
+
+        Some(match self {
+            Self::Cell(a) => Self::Range { from: *a, to: *a }.checked_extend(direction, n)?,
+
+            Self::ColumnRange { from, to } => {
+                let left = cmp::min(*from, *to);
+                let right = cmp::max(*from, *to);
+
+                match direction {
+                    Direction::Left => Self::ColumnRange {
+                        from: left.with_x(left.x.checked_sub(n)?),
+                        to: right,
+                    },
+                    Direction::Right => Self::ColumnRange {
+                        from: left,
+                        to: right.with_x(right.x.checked_add(n)?),
+                    },
+                    Direction::Up | Direction::Down => self.clone(),
+                }
+            }
+
+            Self::NonContiguous(range_or_cells) => Self::NonContiguous(
+                range_or_cells
+                    .iter()
+                    .map(|r| r.checked_extend(direction, n))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+
+            Self::Range { from, to } => {
+                let (mut top_left, mut bottom_right) = Address::corners(from, to);
+
+                match direction {
+                    Direction::Up => {
+                        top_left.row = top_left.row.with_y(top_left.row.y.checked_sub(n)?)
+                    }
+                    Direction::Down => {
+                        bottom_right.row =
+                            bottom_right.row.with_y(bottom_right.row.y.checked_add(n)?)
+                    }
+                    Direction::Left => {
+                        top_left.column = top_left.column.with_x(top_left.column.x.checked_sub(n)?)
+                    }
+                    Direction::Right => {
+                        bottom_right.column = bottom_right
+                            .column
+                            .with_x(bottom_right.column.x.checked_add(n)?)
+                    }
+                }
+
+                Self::Range {
+                    from: top_left,
+                    to: bottom_right,
+                }
+            }
+
+            Self::RowRange { from, to } => {
+                let top = cmp::min(*from, *to);
+                let bottom = cmp::max(*from, *to);
+
+                match direction {
+                    Direction::Up => Self::RowRange {
+                        from: top.with_y(top.y.checked_sub(n)?),
+                        to: bottom,
+                    },
+                    Direction::Down => Self::RowRange {
+                        from: top,
+                        to: bottom.with_y(bottom.y.checked_add(n)?),
+                    },
+                    Direction::Left | Direction::Right => self.clone(),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn r(s: &str) -> RangeOrCell {
+        RangeOrCell::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn offset_cell() {
+        assert_eq!(r("B2").offset(2, 1).unwrap().to_string(), "C4");
+        assert_eq!(r("B2").offset(-1, -1).unwrap().to_string(), "A1");
+        assert!(r("B2").offset(-2, 0).is_err());
+    }
+
+    #[test]
+    fn offset_keeps_absolute() {
+        assert_eq!(r("$B2").offset(1, 1).unwrap().to_string(), "$C3");
+        assert_eq!(r("$A$1:B$2").offset(1, 1).unwrap().to_string(), "$B$2:C$3");
+    }
+
+    #[test]
+    fn offset_column_range() {
+        assert_eq!(r("B:C").offset(10, 1).unwrap().to_string(), "C:D");
+        assert!(r("B:C").offset(0, -2).is_err());
+    }
+
+    #[test]
+    fn offset_non_contiguous() {
+        assert_eq!(
+            r("A1,C3:D4").offset(1, 1).unwrap(),
+            RangeOrCell::NonContiguous(vec![r("B2"), r("D4:E5")])
+        );
+        assert!(r("A1,C3:D4").offset(-1, 0).is_err());
+    }
+
+    #[test]
+    fn offset_row_range() {
+        assert_eq!(r("2:3").offset(-1, 10).unwrap().to_string(), "1:2");
+        assert!(r("2:3").offset(-2, 0).is_err());
+    }
+
+    #[test]
+    fn resize_cell() {
+        assert_eq!(
+            r("B2").resize(Some(3), Some(2)).unwrap().to_string(),
+            "B2:C4"
+        );
+        assert_eq!(r("B2").resize(None, None).unwrap().to_string(), "B2");
+        assert!(matches!(
+            r("B2").resize(Some(0), None),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+        assert!(matches!(
+            r("B3").resize(Some(Index::MAX), None),
+            Err(Error::OutOfBoundsError { .. })
+        ));
+    }
+
+    #[test]
+    fn resize_column_range() {
+        assert_eq!(r("B:C").resize(None, Some(3)).unwrap().to_string(), "B:D");
+        assert_eq!(r("C:B").resize(Some(5), None).unwrap().to_string(), "B1:C5");
+    }
+
+    #[test]
+    fn resize_range() {
+        assert_eq!(
+            r("B2:D4").resize(Some(1), None).unwrap().to_string(),
+            "B2:D2"
+        );
+        assert_eq!(
+            r("D4:B2").resize(None, Some(1)).unwrap().to_string(),
+            "B2:B4"
+        );
+        assert_eq!(
+            r("B2:D4").resize(Some(1), Some(1)).unwrap().to_string(),
+            "B2"
+        );
+        assert!(matches!(
+            r("B2:D4").resize(None, Some(0)),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+    }
+
+    #[test]
+    fn resize_row_range() {
+        assert_eq!(r("2:3").resize(Some(1), None).unwrap().to_string(), "2:2");
+        assert_eq!(r("2:3").resize(None, Some(2)).unwrap().to_string(), "A2:B3");
+    }
+
+    #[test]
+    fn expand_to_cell() {
+        assert_eq!(r("C3").expand_to((0, 4).into()).to_string(), "A3:C5");
+        assert_eq!(r("C3").expand_to((2, 2).into()).to_string(), "C3");
+    }
+
+    #[test]
+    fn expand_to_column_range() {
+        assert_eq!(r("C:D").expand_to((0, 100).into()).to_string(), "A:D");
+    }
+
+    #[test]
+    fn expand_to_non_contiguous() {
+        assert_eq!(
+            r("A1,C3").expand_to((3, 3).into()),
+            RangeOrCell::NonContiguous(vec![r("A1"), r("C3:D4")])
+        );
+    }
+
+    #[test]
+    fn expand_to_range() {
+        assert_eq!(r("B2:C3").expand_to((5, 0).into()).to_string(), "B1:F3");
+        assert_eq!(r("B2:C3").expand_to((1, 1).into()).to_string(), "B2:C3");
+    }
+
+    #[test]
+    fn expand_to_row_range() {
+        assert_eq!(r("3:4").expand_to((10, 9).into()).to_string(), "3:10");
+    }
+
+    #[test]
+    fn extend_cell() {
+        assert_eq!(
+            r("B2").extend(Direction::Down, 2).unwrap().to_string(),
+            "B2:B4"
+        );
+        assert_eq!(
+            r("B2").extend(Direction::Left, 1).unwrap().to_string(),
+            "A2:B2"
+        );
+        assert!(r("B2").extend(Direction::Up, 2).is_err());
+    }
+
+    #[test]
+    fn extend_column_range() {
+        assert_eq!(
+            r("B:C").extend(Direction::Left, 1).unwrap().to_string(),
+            "A:C"
+        );
+        assert_eq!(
+            r("B:C").extend(Direction::Down, 1).unwrap().to_string(),
+            "B:C"
+        );
+        assert!(r("B:C").extend(Direction::Left, 2).is_err());
+    }
+
+    #[test]
+    fn extend_range() {
+        assert_eq!(
+            r("B2:C3").extend(Direction::Up, 1).unwrap().to_string(),
+            "B1:C3"
+        );
+        assert_eq!(
+            r("B2:C3").extend(Direction::Right, 2).unwrap().to_string(),
+            "B2:E3"
+        );
+    }
+
+    #[test]
+    fn extend_row_range() {
+        assert_eq!(
+            r("2:3").extend(Direction::Down, 2).unwrap().to_string(),
+            "2:5"
+        );
+        assert_eq!(
+            r("2:3").extend(Direction::Right, 2).unwrap().to_string(),
+            "2:3"
+        );
+    }
+}
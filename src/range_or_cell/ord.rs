@@ -0,0 +1,115 @@
+use super::RangeOrCell;
+use crate::Index;
+use std::cmp;
+
+impl RangeOrCell {
+    /// The `(row, column)` of the top-left corner of the area covered by the reference.
+    fn position(&self) -> (Index, Index) {
+        match self {
+            Self::Cell(a) => (a.row.y, a.column.x),
+            Self::ColumnRange { from, to } => (0, cmp::min(from.x, to.x)),
+            Self::NonContiguous(range_or_cells) => range_or_cells
+                .iter()
+                .map(Self::position)
+                .reduce(|(top, left), (t, l)| (cmp::min(top, t), cmp::min(left, l)))
+                .unwrap_or((0, 0)),
+            Self::Range { from, to } => (
+                cmp::min(from.row.y, to.row.y),
+                cmp::min(from.column.x, to.column.x),
+            ),
+            Self::RowRange { from, to } => (cmp::min(from.y, to.y), 0),
+        }
+    }
+
+    fn variant_order(&self) -> u8 {
+        match self {
+            Self::Cell(_) => 0,
+            Self::ColumnRange { .. } => 1,
+            Self::NonContiguous(_) => 2,
+            Self::Range { .. } => 3,
+            Self::RowRange { .. } => 4,
+        }
+    }
+}
+
+/// References are ordered by the position of their top-left corner in row-major order (so
+/// `B1` comes before `A2`), then by variant (in the order they're declared) and finally by their
+/// `from`/`to` (or members) so that it agrees with `Eq`.
+impl Ord for RangeOrCell {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.position()
+            .cmp(&other.position())
+            .then_with(|| self.variant_order().cmp(&other.variant_order()))
+            .then_with(|| match (self, other) {
+                (Self::Cell(a), Self::Cell(b)) => a.cmp(b),
+
+                (Self::ColumnRange { from, to }, Self::ColumnRange { from: of, to: ot }) => {
+                    (from, to).cmp(&(of, ot))
+                }
+
+                (Self::NonContiguous(a), Self::NonContiguous(b)) => a.cmp(b),
+
+                (Self::Range { from, to }, Self::Range { from: of, to: ot }) => {
+                    (from, to).cmp(&(of, ot))
+                }
+
+                (Self::RowRange { from, to }, Self::RowRange { from: of, to: ot }) => {
+                    (from, to).cmp(&(of, ot))
+                }
+
+                // the variants are the same by now
+                _ => cmp::Ordering::Equal,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn r(s: &str) -> RangeOrCell {
+        RangeOrCell::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn cmp_row_major() {
+        assert!(r("B1") < r("A2"));
+        assert!(r("A2") < r("B2"));
+        assert!(r("A1:C3") < r("B1"));
+    }
+
+    #[test]
+    fn cmp_variant() {
+        assert!(r("A1") < r("A:A"));
+        assert!(r("A:A") < r("A1:B2"));
+        assert!(r("A1:B2") < r("1:1"));
+    }
+
+    #[test]
+    fn cmp_same_position() {
+        assert!(r("A1:B2") < r("A1:C2"));
+        assert!(r("A1") < r("$A1"));
+        assert_eq!(r("A1:B2").cmp(&r("A1:B2")), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn cmp_top_left_corner() {
+        // the corner of the whole area, even when no one cell of it is there
+        assert!(r("D4:B2") < r("C2"));
+        assert!(r("C1:D5,A3") < r("B1"));
+        assert!(r("B:C") < r("A2"));
+        assert!(r("3:4") > r("Z2"));
+    }
+
+    #[test]
+    fn sort() {
+        let mut refs = [r("C3"), r("2:2"), r("A1:B2"), r("B:B"), r("B1")];
+        refs.sort();
+
+        assert_eq!(
+            refs.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+            vec!["A1:B2", "B1", "B:B", "2:2", "C3"]
+        );
+    }
+}
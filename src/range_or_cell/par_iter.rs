@@ -0,0 +1,258 @@
+//! Iterating over a reference in parallel with [rayon](https://docs.rs/rayon) (behind the
+//! `rayon` feature).  The items are the same as `iter` and come out in the same order when
+//! collected.
+//!
+//! The work is split up between the members of a `NonContiguous` and then within a member by
+//! position, which for a `Range` means between rows (and between columns once it's down to a
+//! single row).
+use super::iterator::RangeOrCellIterator;
+use super::RangeOrCell;
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use std::collections::VecDeque;
+use std::iter;
+
+/// A parallel iterator over a `RangeOrCell`, as returned by `RangeOrCell::par_iter`.
+#[derive(Clone, Debug)]
+pub struct RangeOrCellParIter {
+    pieces: Pieces,
+}
+
+/// The iterators for each contiguous member of the reference (with any nested `NonContiguous`s
+/// flattened out), which are visited one after the other.
+#[derive(Clone, Debug)]
+pub(crate) struct Pieces {
+    iters: VecDeque<RangeOrCellIterator>,
+}
+
+fn flatten(range_or_cell: &RangeOrCell, iters: &mut VecDeque<RangeOrCellIterator>) {
+    if let RangeOrCell::NonContiguous(range_or_cells) = range_or_cell {
+        for r in range_or_cells {
+            flatten(r, iters);
+        }
+    } else {
+        iters.push_back(range_or_cell.iter());
+    }
+}
+
+/// Split the iterator of a range so that the first `index` of its remaining items are on the
+/// left
+fn split_iter(i: RangeOrCellIterator, index: u128) -> (RangeOrCellIterator, RangeOrCellIterator) {
+    let (mut left, mut right) = (i.clone(), i);
+
+    match (&mut left, &mut right) {
+        (
+            RangeOrCellIterator::ColumnRange { back, .. }
+            | RangeOrCellIterator::Range { back, .. }
+            | RangeOrCellIterator::RowRange { back, .. },
+            RangeOrCellIterator::ColumnRange { front, .. }
+            | RangeOrCellIterator::Range { front, .. }
+            | RangeOrCellIterator::RowRange { front, .. },
+        ) => {
+            *front += index;
+            *back = *front;
+        }
+
+        _ => unreachable!("only ranges have more than one item to split"),
+    }
+
+    (left, right)
+}
+
+impl Pieces {
+    fn remaining(&self) -> u128 {
+        self.iters
+            .iter()
+            .fold(0, |n, i| n.saturating_add(i.remaining()))
+    }
+}
+
+impl Producer for Pieces {
+    type Item = RangeOrCell;
+    type IntoIter = Self;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self
+    }
+
+    fn split_at(mut self, index: usize) -> (Self, Self) {
+        let mut index = index as u128;
+        let mut left = VecDeque::new();
+
+        // whole members go to the left until we get to the one the split is in
+        while let Some(i) = self.iters.pop_front() {
+            let remaining = i.remaining();
+            if index >= remaining {
+                index -= remaining;
+                left.push_back(i);
+                continue;
+            }
+
+            if index > 0 {
+                let (l, r) = split_iter(i, index);
+                left.push_back(l);
+                self.iters.push_front(r);
+            } else {
+                self.iters.push_front(i);
+            }
+            break;
+        }
+
+        (Self { iters: left }, self)
+    }
+}
+
+impl iter::Iterator for Pieces {
+    type Item = RangeOrCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(i) = self.iters.front_mut() {
+            if let Some(r) = i.next() {
+                return Some(r);
+            }
+            self.iters.pop_front();
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining()) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl iter::DoubleEndedIterator for Pieces {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(i) = self.iters.back_mut() {
+            if let Some(r) = i.next_back() {
+                return Some(r);
+            }
+            self.iters.pop_back();
+        }
+        None
+    }
+}
+
+impl iter::ExactSizeIterator for Pieces {}
+
+impl ParallelIterator for RangeOrCellParIter {
+    type Item = RangeOrCell;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        usize::try_from(self.pieces.remaining()).ok()
+    }
+}
+
+/// Like `RangeOrCellIterator`, a range with more than `usize::MAX` cells will panic if it's
+/// used as an indexed iterator.
+impl IndexedParallelIterator for RangeOrCellParIter {
+    fn len(&self) -> usize {
+        self.pieces.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self.pieces)
+    }
+}
+
+impl IntoParallelIterator for &RangeOrCell {
+    type Item = RangeOrCell;
+    type Iter = RangeOrCellParIter;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter()
+    }
+}
+
+impl RangeOrCell {
+    /// Iterate over the reference in parallel.  It emits the same items as `iter` (and in the
+    /// same order, if you `collect` them).
+    pub fn par_iter(&self) -> RangeOrCellParIter {
+        let mut iters = VecDeque::new();
+        flatten(self, &mut iters);
+
+        RangeOrCellParIter {
+            pieces: Pieces { iters },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rayon::prelude::*;
+    use std::str::FromStr;
+
+    fn assert_same_as_iter(r: &str) {
+        let r = RangeOrCell::from_str(r).unwrap();
+
+        assert_eq!(
+            r.par_iter().collect::<Vec<_>>(),
+            r.iter().collect::<Vec<_>>(),
+            "{r}"
+        );
+        assert_eq!(r.par_iter().count(), r.iter().len(), "{r}");
+    }
+
+    #[test]
+    fn par_iter_matches_iter() {
+        for r in [
+            "B2",
+            "A1:Z1000",
+            "Z1000:A1",
+            "$A1:C3",
+            "A:Z",
+            "1:100",
+            "A1:C3,B2:D4,E5",
+            "A1,B2,C3,D4,E5,F6",
+        ] {
+            assert_same_as_iter(r);
+        }
+    }
+
+    #[test]
+    fn par_iter_nested_non_contiguous() {
+        let r = RangeOrCell::NonContiguous(vec![
+            RangeOrCell::from_str("A1:B2").unwrap(),
+            RangeOrCell::from_str("C3,D4:E500").unwrap(),
+        ]);
+
+        assert_eq!(
+            r.par_iter().collect::<Vec<_>>(),
+            r.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn par_iter_indexed() {
+        let r = RangeOrCell::from_str("A1:J10,K1:K10").unwrap();
+
+        assert_eq!(r.par_iter().len(), 110);
+        assert_eq!(
+            r.par_iter().skip(99).take(2).collect::<Vec<_>>(),
+            r.iter().skip(99).take(2).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            r.par_iter().rev().collect::<Vec<_>>(),
+            r.iter().rev().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            (&r).into_par_iter()
+                .position_first(|c| c.to_string() == "K5"),
+            Some(104)
+        );
+    }
+}
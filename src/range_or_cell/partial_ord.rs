@@ -0,0 +1,22 @@
+use super::RangeOrCell;
+use std::cmp;
+
+impl PartialOrd for RangeOrCell {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn partial_cmp() {
+        assert_eq!(
+            RangeOrCell::column(3).partial_cmp(&RangeOrCell::Cell((0, 0).into())),
+            Some(Ordering::Greater)
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Adjusting references when rows or columns are inserted or deleted, the same way a spreadsheet
+//! engine rewrites the references in formulas, named ranges and validation rules.
+use super::RangeOrCell;
+use crate::{Column, Index, Row};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Edit {
+    /// `n` rows or columns inserted before index `at`
+    Insert { at: Index, n: Index },
+
+    /// `n` rows or columns deleted starting at index `at`
+    Delete { at: Index, n: Index },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Axis {
+    Columns,
+    Rows,
+}
+
+impl Edit {
+    /// Adjust the (inclusive) span `a..=b` (which can be in either order), returning `None` if
+    /// the whole thing was deleted.
+    fn adjust(&self, a: Index, b: Index) -> Option<(Index, Index)> {
+        let (start, end) = (a.min(b), a.max(b));
+
+        let (start, end) = match *self {
+            // everything at or after `at` moves down/right.  so a span that straddles `at` grows
+            Self::Insert { at, n } => {
+                let shift = |i: Index| if i >= at { i.checked_add(n) } else { Some(i) };
+                (shift(start)?, shift(end)?)
+            }
+
+            Self::Delete { at, n } => {
+                let last_deleted = at.saturating_add(n - 1);
+                if start >= at && end <= last_deleted {
+                    return None;
+                }
+
+                // the ends that were deleted snap to the edge of what's left
+                let start = if start < at {
+                    start
+                } else if start <= last_deleted {
+                    at
+                } else {
+                    start - n
+                };
+                let end = if end < at {
+                    end
+                } else if end <= last_deleted {
+                    at - 1
+                } else {
+                    end - n
+                };
+
+                (start, end)
+            }
+        };
+
+        Some(if a <= b { (start, end) } else { (end, start) })
+    }
+
+    fn adjust_columns(&self, a: &Column, b: &Column) -> Option<(Column, Column)> {
+        let (x_a, x_b) = self.adjust(a.x, b.x)?;
+        Some((a.with_x(x_a), b.with_x(x_b)))
+    }
+
+    fn adjust_rows(&self, a: &Row, b: &Row) -> Option<(Row, Row)> {
+        let (y_a, y_b) = self.adjust(a.y, b.y)?;
+        Some((a.with_y(y_a), b.with_y(y_b)))
+    }
+}
+
+impl RangeOrCell {
+    /// Adjust the reference for `n` rows being inserted before (zero-based) row `at`, like
+    /// inserting rows in a spreadsheet.  Anything at or below `at` moves down and a range that
+    /// spans `at` grows.  `ColumnRange`s aren't affected.  Both relative and `absolute`
+    /// components are adjusted.
+    ///
+    /// Returns `None` (a `#REF!`) if the reference would be pushed off of the grid.
+    pub fn insert_rows(&self, at: Index, n: Index) -> Option<Self> {
+        self.structural_edit(Axis::Rows, Edit::Insert { at, n })
+    }
+
+    /// Adjust the reference for `n` rows being deleted starting at (zero-based) row `at`.
+    /// Anything below the deleted rows moves up and a range that partially overlaps them
+    /// shrinks.  `ColumnRange`s aren't affected.
+    ///
+    /// Returns `None` (a `#REF!`) if every cell of the reference was deleted.  A `NonContiguous`
+    /// is a `#REF!` if any one of its members is - a spreadsheet leaves a `#REF!` in place of
+    /// that member, which makes the whole union invalid.
+    pub fn delete_rows(&self, at: Index, n: Index) -> Option<Self> {
+        self.structural_edit(Axis::Rows, Edit::Delete { at, n })
+    }
+
+    /// Adjust the reference for `n` columns being inserted before (zero-based) column `at`.  See
+    /// `insert_rows`, which this mirrors.
+    pub fn insert_columns(&self, at: Index, n: Index) -> Option<Self> {
+        self.structural_edit(Axis::Columns, Edit::Insert { at, n })
+    }
+
+    /// Adjust the reference for `n` columns being deleted starting at (zero-based) column `at`.
+    /// See `delete_rows`, which this mirrors.
+    pub fn delete_columns(&self, at: Index, n: Index) -> Option<Self> {
+        self.structural_edit(Axis::Columns, Edit::Delete { at, n })
+    }
+
+    fn structural_edit(&self, axis: Axis, edit: Edit) -> Option<Self> {
+        if let Edit::Insert { n: 0, .. } | Edit::Delete { n: 0, .. } = edit {
+            return Some(self.clone());
+        }
+
+        Some(match self {
+            Self::Cell(a) => {
+                let mut a = *a;
+                match axis {
+                    Axis::Columns => (a.column, _) = edit.adjust_columns(&a.column, &a.column)?,
+                    Axis::Rows => (a.row, _) = edit.adjust_rows(&a.row, &a.row)?,
+                }
+                Self::Cell(a)
+            }
+
+            Self::ColumnRange { from, to } if axis == Axis::Columns => {
+                let (from, to) = edit.adjust_columns(from, to)?;
+                Self::ColumnRange { from, to }
+            }
+
+            Self::NonContiguous(range_or_cells) => Self::NonContiguous(
+                range_or_cells
+                    .iter()
+                    .map(|r| r.structural_edit(axis, edit))
+                    .collect::<Option<_>>()?,
+            ),
+
+            Self::Range { from, to } => {
+                let (mut from, mut to) = (*from, *to);
+                match axis {
+                    Axis::Columns => {
+                        (from.column, to.column) = edit.adjust_columns(&from.column, &to.column)?
+                    }
+                    Axis::Rows => (from.row, to.row) = edit.adjust_rows(&from.row, &to.row)?,
+                }
+                Self::Range { from, to }
+            }
+
+            Self::RowRange { from, to } if axis == Axis::Rows => {
+                let (from, to) = edit.adjust_rows(from, to)?;
+                Self::RowRange { from, to }
+            }
+
+            // whole columns don't change when rows are inserted or deleted (and vice versa)
+            Self::ColumnRange { .. } | Self::RowRange { .. } => self.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn r(s: &str) -> RangeOrCell {
+        RangeOrCell::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn insert_rows_cell() {
+        assert_eq!(r("B5").insert_rows(4, 3), Some(r("B8")));
+        assert_eq!(r("B5").insert_rows(5, 3), Some(r("B5")));
+        assert_eq!(r("$B$5").insert_rows(0, 1), Some(r("$B$6")));
+    }
+
+    #[test]
+    fn insert_rows_range() {
+        // inserting inside of the range grows it
+        assert_eq!(r("A2:C10").insert_rows(4, 3), Some(r("A2:C13")));
+        // inserting above it moves it
+        assert_eq!(r("A2:C10").insert_rows(1, 3), Some(r("A5:C13")));
+        // and below it does nothing
+        assert_eq!(r("A2:C10").insert_rows(10, 3), Some(r("A2:C10")));
+        // it keeps the direction of the range
+        assert_eq!(r("C10:A2").insert_rows(4, 3), Some(r("C13:A2")));
+    }
+
+    #[test]
+    fn insert_rows_column_and_row_ranges() {
+        assert_eq!(r("A:C").insert_rows(0, 3), Some(r("A:C")));
+        assert_eq!(r("2:4").insert_rows(2, 3), Some(r("2:7")));
+    }
+
+    #[test]
+    fn insert_rows_off_grid() {
+        assert_eq!(
+            RangeOrCell::Cell((0, Index::MAX).into()).insert_rows(0, 1),
+            None
+        );
+    }
+
+    #[test]
+    fn delete_rows_cell() {
+        assert_eq!(r("B5").delete_rows(4, 1), None);
+        assert_eq!(r("B5").delete_rows(0, 2), Some(r("B3")));
+        assert_eq!(r("B5").delete_rows(5, 2), Some(r("B5")));
+    }
+
+    #[test]
+    fn delete_rows_range() {
+        // deleting from the middle shrinks it
+        assert_eq!(r("A2:C10").delete_rows(4, 3), Some(r("A2:C7")));
+        // deleting across the top edge
+        assert_eq!(r("A2:C10").delete_rows(0, 4), Some(r("A1:C6")));
+        // deleting across the bottom edge
+        assert_eq!(r("A2:C10").delete_rows(8, 10), Some(r("A2:C8")));
+        // and deleting the whole thing
+        assert_eq!(r("A2:C10").delete_rows(1, 9), None);
+    }
+
+    #[test]
+    fn delete_rows_row_range() {
+        assert_eq!(r("2:4").delete_rows(0, 2), Some(r("1:2")));
+        assert_eq!(r("2:4").delete_rows(1, 3), None);
+        assert_eq!(r("A:A").delete_rows(0, 5), Some(r("A:A")));
+    }
+
+    #[test]
+    fn delete_rows_non_contiguous() {
+        assert_eq!(r("A1:A2,A5:B6").delete_rows(0, 1), Some(r("A1:A1,A4:B5")));
+        // a member that's gone is a `#REF!` in the union, so the whole thing is
+        assert_eq!(r("A1,A5:B6").delete_rows(0, 1), None);
+        assert_eq!(r("A1,A2").delete_rows(0, 2), None);
+        assert_eq!(r("A1,B2").insert_rows(1, Index::MAX), None);
+    }
+
+    #[test]
+    fn insert_columns() {
+        assert_eq!(r("B2:D4").insert_columns(2, 2), Some(r("B2:F4")));
+        assert_eq!(r("B:D").insert_columns(0, 2), Some(r("D:F")));
+        assert_eq!(r("2:4").insert_columns(0, 2), Some(r("2:4")));
+    }
+
+    #[test]
+    fn delete_columns() {
+        // deleting C:E
+        assert_eq!(r("A1:F1").delete_columns(2, 3), Some(r("A1:C1")));
+        assert_eq!(r("D1").delete_columns(2, 3), None);
+        assert_eq!(r("$G$1").delete_columns(2, 3), Some(r("$D$1")));
+        assert_eq!(r("C:E").delete_columns(2, 3), None);
+        assert_eq!(r("B:F").delete_columns(2, 3), Some(r("B:C")));
+    }
+}
@@ -0,0 +1,140 @@
+use super::RangeOrCell;
+use crate::{Address, Error, Result};
+
+impl RangeOrCell {
+    /// Flip the reference across the diagonal, as if the grid were transposed around `A1` (like
+    /// Sheets' `TRANSPOSE` or paste-special transpose).  Columns and rows swap places along with
+    /// their `absolute` flags, so a `ColumnRange` becomes a `RowRange` and vice-versa.
+    pub fn transpose(&self) -> Self {
+        match self {
+            Self::Cell(a) => Self::Cell(a.transpose()),
+
+            Self::ColumnRange { from, to } => Self::RowRange {
+                from: from.transpose(),
+                to: to.transpose(),
+            },
+
+            Self::NonContiguous(range_or_cells) => {
+                Self::NonContiguous(range_or_cells.iter().map(|r| r.transpose()).collect())
+            }
+
+            Self::Range { from, to } => Self::Range {
+                from: from.transpose(),
+                to: to.transpose(),
+            },
+
+            Self::RowRange { from, to } => Self::ColumnRange {
+                from: from.transpose(),
+                to: to.transpose(),
+            },
+        }
+    }
+
+    /// Transpose the reference around `anchor`, which is the one cell that stays in place.  This
+    /// is where a block lands when it's pasted-transposed with `anchor` as its top-left corner.
+    ///
+    /// Returns an `Error::OutOfBoundsError` if any part would land off of the grid.
+    pub fn transpose_around(&self, anchor: &Address) -> Result<Self> {
+        self.checked_transpose_around(anchor).ok_or_else(|| {
+            Error::out_of_bounds(self, format!("Transposing around {anchor} leaves the grid"))
+        })
+    }
+
+    fn checked_transpose_around(&self, anchor: &Address) -> Option<Self> {
+        Some(match self {
+            Self::Cell(a) => Self::Cell(a.transpose_around(anchor)?),
+
+            Self::ColumnRange { from, to } => Self::RowRange {
+                from: from.transpose_around(anchor)?,
+                to: to.transpose_around(anchor)?,
+            },
+
+            Self::NonContiguous(range_or_cells) => Self::NonContiguous(
+                range_or_cells
+                    .iter()
+                    .map(|r| r.checked_transpose_around(anchor))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+
+            Self::Range { from, to } => Self::Range {
+                from: from.transpose_around(anchor)?,
+                to: to.transpose_around(anchor)?,
+            },
+
+            Self::RowRange { from, to } => Self::ColumnRange {
+                from: from.transpose_around(anchor)?,
+                to: to.transpose_around(anchor)?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn r(s: &str) -> RangeOrCell {
+        RangeOrCell::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn transpose_cell() {
+        assert_eq!(r("C2").transpose().to_string(), "B3");
+        assert_eq!(r("$C2").transpose().to_string(), "B$3");
+    }
+
+    #[test]
+    fn transpose_column_range() {
+        assert_eq!(r("A:C").transpose().to_string(), "1:3");
+        assert_eq!(r("$B:C").transpose().to_string(), "$2:3");
+    }
+
+    #[test]
+    fn transpose_non_contiguous() {
+        assert_eq!(
+            r("A1,B:B,2:2").transpose(),
+            RangeOrCell::NonContiguous(vec![r("A1"), r("2:2"), r("B:B")])
+        );
+    }
+
+    #[test]
+    fn transpose_range() {
+        assert_eq!(r("A1:C2").transpose().to_string(), "A1:B3");
+        assert_eq!(r("A1:C2").transpose().transpose(), r("A1:C2"));
+    }
+
+    #[test]
+    fn transpose_row_range() {
+        assert_eq!(r("1:3").transpose().to_string(), "A:C");
+    }
+
+    #[test]
+    fn transpose_around_range() {
+        // a 2-wide x 3-tall block anchored at C3 becomes 3-wide x 2-tall
+        assert_eq!(
+            r("C3:D5")
+                .transpose_around(&(2, 2).into())
+                .unwrap()
+                .to_string(),
+            "C3:E4"
+        );
+    }
+
+    #[test]
+    fn transpose_around_column_range() {
+        assert_eq!(
+            r("C:D")
+                .transpose_around(&(2, 9).into())
+                .unwrap()
+                .to_string(),
+            "10:11"
+        );
+    }
+
+    #[test]
+    fn transpose_around_err() {
+        assert!(r("A5").transpose_around(&(2, 0).into()).is_err());
+        assert!(r("C1,A5").transpose_around(&(2, 0).into()).is_err());
+    }
+}
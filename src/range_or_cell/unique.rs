@@ -0,0 +1,295 @@
+//! Iterating over the distinct cells of a reference, so that the cells where the members of a
+//! `NonContiguous` overlap are only visited once.  Rather than remembering every cell it's seen,
+//! each row of a member works out which of its cells are covered by the members before it.
+use super::addresses::endpoints;
+use super::RangeOrCell;
+use crate::rect::Rect;
+use crate::{Address, Index};
+use std::{cmp, iter};
+
+/// A contiguous member of the reference (with any nested `NonContiguous`s flattened out)
+#[derive(Clone, Debug)]
+struct Member {
+    from: Address,
+    to: Address,
+    rect: Rect,
+}
+
+fn flatten(range_or_cell: &RangeOrCell, members: &mut Vec<Member>) {
+    if let RangeOrCell::NonContiguous(range_or_cells) = range_or_cell {
+        for r in range_or_cells {
+            flatten(r, members);
+        }
+    } else {
+        let (from, to) = endpoints(range_or_cell);
+        members.push(Member {
+            from,
+            to,
+            rect: range_or_cell.rects()[0],
+        });
+    }
+}
+
+/// The distinct cells of a `RangeOrCell`, as returned by `RangeOrCell::unique_addresses`.
+#[derive(Clone, Debug)]
+pub struct UniqueAddresses {
+    members: Vec<Member>,
+    /// The member being visited
+    m: usize,
+    /// The row of it being visited, or `None` if it hasn't been started yet
+    y: Option<Index>,
+    /// The `(first, last)` columns of the spans left in the row, the next one at the end
+    spans: Vec<(Index, Index)>,
+    /// The next and last columns of the span being visited
+    cursor: Option<(Index, Index)>,
+    /// Scratch space for the parts of a row covered by earlier members
+    covered: Vec<(Index, Index)>,
+}
+
+impl UniqueAddresses {
+    fn new(range_or_cell: &RangeOrCell) -> Self {
+        let mut members = vec![];
+        flatten(range_or_cell, &mut members);
+
+        Self {
+            members,
+            m: 0,
+            y: None,
+            spans: vec![],
+            cursor: None,
+            covered: vec![],
+        }
+    }
+
+    /// Move on to the next row (of this member or the next one) that has any cells which weren't
+    /// in an earlier member, filling in `spans` with them.  Returns `false` once every member is
+    /// done.
+    fn next_row(&mut self) -> bool {
+        loop {
+            let Some(member) = self.members.get(self.m) else {
+                return false;
+            };
+            let (first, last) = (member.from.row.y, member.to.row.y);
+            let down = first <= last;
+
+            let y = match self.y {
+                None => first,
+                Some(y) if y == last => {
+                    self.m += 1;
+                    self.y = None;
+                    continue;
+                }
+                Some(y) if down => y + 1,
+                Some(y) => y - 1,
+            };
+            self.y = Some(y);
+
+            self.fill_spans(y);
+            if !self.spans.is_empty() {
+                return true;
+            }
+
+            // every row up until an earlier member starts or stops is covered in the same way,
+            // so jump to the last one of those
+            let earlier = self.members[..self.m].iter().map(|m| m.rect);
+            let y = if down {
+                earlier
+                    .flat_map(|r| [r.top, r.bottom.saturating_add(1)])
+                    .filter(|&e| e > y && e <= last)
+                    .min()
+                    .map_or(last, |e| e - 1)
+            } else {
+                earlier
+                    .flat_map(|r| [r.bottom, r.top.saturating_sub(1)])
+                    .filter(|&e| e < y && e >= last)
+                    .max()
+                    .map_or(last, |e| e + 1)
+            };
+            self.y = Some(y);
+        }
+    }
+
+    /// The spans of row `y` of the current member which aren't covered by an earlier member, in
+    /// the order they'll be visited
+    fn fill_spans(&mut self, y: Index) {
+        let member = &self.members[self.m];
+        let (left, right) = (member.rect.left, member.rect.right);
+
+        self.covered.clear();
+        self.covered.extend(
+            self.members[..self.m]
+                .iter()
+                .map(|m| m.rect)
+                .filter(|r| r.top <= y && y <= r.bottom && r.left <= right && left <= r.right)
+                .map(|r| (cmp::max(r.left, left), cmp::min(r.right, right))),
+        );
+        self.covered.sort_unstable();
+
+        self.spans.clear();
+        let mut x = Some(left);
+        for &(start, end) in &self.covered {
+            let Some(from) = x else {
+                break;
+            };
+            if start > from {
+                self.spans.push((from, start - 1));
+            }
+            if end >= from {
+                x = end.checked_add(1).filter(|&x| x <= right);
+            }
+        }
+        if let Some(from) = x {
+            self.spans.push((from, right));
+        }
+
+        // `spans` is popped from the end, so it goes in backwards
+        if member.from.column.x <= member.to.column.x {
+            self.spans.reverse();
+        } else {
+            for span in self.spans.iter_mut() {
+                *span = (span.1, span.0);
+            }
+        }
+    }
+}
+
+impl iter::Iterator for UniqueAddresses {
+    type Item = Address;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((x, last)) = self.cursor {
+                let from = &self.members[self.m].from;
+                let address = Address {
+                    column: from.column.with_x(x),
+                    row: from.row.with_y(self.y?),
+                };
+
+                self.cursor = match x.cmp(&last) {
+                    cmp::Ordering::Less => Some((x + 1, last)),
+                    cmp::Ordering::Equal => None,
+                    cmp::Ordering::Greater => Some((x - 1, last)),
+                };
+                return Some(address);
+            }
+
+            if let Some(span) = self.spans.pop() {
+                self.cursor = Some(span);
+            } else if !self.next_row() {
+                return None;
+            }
+        }
+    }
+}
+
+impl iter::FusedIterator for UniqueAddresses {}
+
+impl RangeOrCell {
+    /// Every distinct cell of the reference, so the cells where members of a `NonContiguous`
+    /// overlap are only visited once (for the first member they're in).  Otherwise it's the
+    /// same order as `addresses`.  A `ColumnRange` or `RowRange` goes on until the edge of the
+    /// grid, so you'll want to `take` from those.
+    pub fn unique_addresses(&self) -> UniqueAddresses {
+        UniqueAddresses::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    fn unique_to_strs(r: &str) -> Vec<String> {
+        RangeOrCell::from_str(r)
+            .unwrap()
+            .unique_addresses()
+            .map(|a| a.to_string())
+            .collect()
+    }
+
+    /// `addresses` but dropping the cells that have already been seen
+    fn first_occurrences(r: &RangeOrCell) -> Vec<Address> {
+        let mut seen = HashSet::new();
+        r.addresses().filter(|a| seen.insert(*a)).collect()
+    }
+
+    #[test]
+    fn unique_addresses_overlapping() {
+        assert_eq!(
+            unique_to_strs("A1:C3,B2:D4"),
+            vec![
+                "A1", "B1", "C1", "A2", "B2", "C2", "A3", "B3", "C3", "D2", "D3", "B4", "C4", "D4"
+            ]
+        );
+    }
+
+    #[test]
+    fn unique_addresses_matches_first_occurrences() {
+        for r in [
+            "B2",
+            "A1:B2",
+            "A1:B2,A1:B2",
+            "A1:E5,B2:C3,D4",
+            "C3:A1,B2:D4",
+            "A1:E1,E1:A1,C1",
+            "B2:D4,A3:E3,C1:C5",
+            "D4:B2,A1:C3,E5:C3",
+        ] {
+            let r = RangeOrCell::from_str(r).unwrap();
+            assert_eq!(
+                r.unique_addresses().collect::<Vec<_>>(),
+                first_occurrences(&r),
+                "{r}"
+            );
+        }
+    }
+
+    #[test]
+    fn unique_addresses_nested_non_contiguous() {
+        let r = RangeOrCell::NonContiguous(vec![
+            RangeOrCell::from_str("A1:B1").unwrap(),
+            RangeOrCell::from_str("B1,A1:C1").unwrap(),
+        ]);
+
+        assert_eq!(
+            r.unique_addresses()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["A1", "B1", "C1"]
+        );
+    }
+
+    #[test]
+    fn unique_addresses_covered_member() {
+        // the second and third members are entirely covered, so they're skipped over rather than
+        // walked down row-by-row
+        let r = RangeOrCell::from_str("A1:B1000000,B1000000:A1,A:A,C1").unwrap();
+        let mut unique = r.unique_addresses().skip(1_999_999);
+
+        assert_eq!(unique.next().unwrap().to_string(), "B1000000");
+        assert_eq!(unique.next().unwrap().to_string(), "A1000001");
+    }
+
+    #[test]
+    fn unique_addresses_column_and_row_ranges() {
+        assert_eq!(
+            RangeOrCell::from_str("A:A,A1:B2")
+                .unwrap()
+                .unique_addresses()
+                .take(3)
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["A1", "A2", "A3"]
+        );
+        assert_eq!(
+            RangeOrCell::from_str("2:2,A2")
+                .unwrap()
+                .unique_addresses()
+                .take(2)
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["A2", "B2"]
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Sliding a window over a reference, for rolling calculations like a moving average down a
+//! column or a 3x3 kernel over a grid.
+use super::{cell_or_range, RangeOrCell};
+use crate::rect::Rect;
+use crate::{Address, Error, Index, Result};
+use std::{iter, vec};
+
+/// Slides a window over a single (contiguous) reference, left-to-right then top-to-bottom.
+#[derive(Clone, Debug)]
+struct Sliding {
+    rect: Rect,
+    height: Index,
+    width: Index,
+    row_stride: Index,
+    column_stride: Index,
+    /// The top-left of the next window
+    current: Option<(Index, Index)>,
+}
+
+impl Sliding {
+    fn new(
+        rect: Rect,
+        (height, width): (Index, Index),
+        (row_stride, column_stride): (Index, Index),
+    ) -> Self {
+        // a window that doesn't fit doesn't slide anywhere
+        let fits = rect.right - rect.left >= width - 1 && rect.bottom - rect.top >= height - 1;
+
+        Self {
+            rect,
+            height,
+            width,
+            row_stride,
+            column_stride,
+            current: fits.then_some((rect.left, rect.top)),
+        }
+    }
+
+    /// Where a window starting at `start` (and `size` long) can move to next, if there's room
+    fn slide(start: Index, size: Index, stride: Index, end: Index) -> Option<Index> {
+        let next = start.checked_add(stride)?;
+        (next <= end && end - next >= size - 1).then_some(next)
+    }
+}
+
+impl iter::Iterator for Sliding {
+    type Item = RangeOrCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.current?;
+
+        self.current = match Self::slide(x, self.width, self.column_stride, self.rect.right) {
+            Some(x) => Some((x, y)),
+            None => Self::slide(y, self.height, self.row_stride, self.rect.bottom)
+                .map(|y| (self.rect.left, y)),
+        };
+
+        Some(cell_or_range(
+            Address::new(x, y),
+            Address::new(x + (self.width - 1), y + (self.height - 1)),
+        ))
+    }
+}
+
+/// The windows of a `RangeOrCell`, as returned by `RangeOrCell::windows`.
+#[derive(Clone, Debug)]
+pub struct RangeOrCellWindows {
+    current: Option<Sliding>,
+    slidings: vec::IntoIter<Sliding>,
+}
+
+impl iter::Iterator for RangeOrCellWindows {
+    type Item = RangeOrCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(window) = self.current.as_mut().and_then(|s| s.next()) {
+                return Some(window);
+            }
+
+            self.current = Some(self.slidings.next()?);
+        }
+    }
+}
+
+impl iter::FusedIterator for RangeOrCellWindows {}
+
+impl RangeOrCell {
+    /// Every `height` by `width` window of the reference, sliding one column at a time and then
+    /// one row at a time: the 2x2 windows of `A1:C3` are `A1:B2`, `B1:C2`, `A2:B3` and `B2:C3`.
+    /// Only whole windows are produced, so a reference smaller than the window has none.  Like
+    /// `chunks`, the windows are always relative, a window of a single cell is a `Cell` and
+    /// each member of a `NonContiguous` is done in turn.
+    ///
+    /// A `ColumnRange` or `RowRange` slides on until the edge of the grid, so you'll want to
+    /// `take` from those.
+    ///
+    /// Returns an `Error::InvalidArgumentError` if `height` or `width` is zero.
+    pub fn windows(&self, height: Index, width: Index) -> Result<RangeOrCellWindows> {
+        self.windows_with_stride(height, width, 1, 1)
+    }
+
+    /// Like `windows` but sliding `row_stride` rows and `column_stride` columns at a time.  So
+    /// windows with the same size as their stride don't overlap.
+    ///
+    /// Returns an `Error::InvalidArgumentError` if the size or a stride is zero.
+    pub fn windows_with_stride(
+        &self,
+        height: Index,
+        width: Index,
+        row_stride: Index,
+        column_stride: Index,
+    ) -> Result<RangeOrCellWindows> {
+        if height == 0 || width == 0 {
+            return Err(Error::invalid_argument(
+                self,
+                format!("Windows must be at least one cell in size, not {height}x{width}"),
+            ));
+        } else if row_stride == 0 || column_stride == 0 {
+            return Err(Error::invalid_argument(
+                self,
+                format!("Windows must move by at least one cell, not {row_stride}x{column_stride}"),
+            ));
+        }
+
+        Ok(RangeOrCellWindows {
+            current: None,
+            slidings: self
+                .rects()
+                .into_iter()
+                .map(|rect| Sliding::new(rect, (height, width), (row_stride, column_stride)))
+                .collect::<Vec<_>>()
+                .into_iter(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn windows_to_strs(s: &str, height: Index, width: Index) -> Vec<String> {
+        RangeOrCell::from_str(s)
+            .unwrap()
+            .windows(height, width)
+            .unwrap()
+            .map(|r| r.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn windows_down_a_column() {
+        assert_eq!(
+            windows_to_strs("B2:B6", 3, 1),
+            vec!["B2:B4", "B3:B5", "B4:B6"]
+        );
+    }
+
+    #[test]
+    fn windows_across_a_row() {
+        assert_eq!(
+            windows_to_strs("A1:D1", 1, 2),
+            vec!["A1:B1", "B1:C1", "C1:D1"]
+        );
+    }
+
+    #[test]
+    fn windows_rectangle() {
+        assert_eq!(
+            windows_to_strs("C3:A1", 2, 2),
+            vec!["A1:B2", "B1:C2", "A2:B3", "B2:C3"]
+        );
+        assert_eq!(
+            windows_to_strs("$A$1:B2", 1, 1),
+            vec!["A1", "B1", "A2", "B2"]
+        );
+    }
+
+    #[test]
+    fn windows_too_big() {
+        assert!(windows_to_strs("A1:C3", 4, 1).is_empty());
+        assert!(windows_to_strs("A1:C3", 1, 4).is_empty());
+        assert_eq!(windows_to_strs("A1:C3", 3, 3), vec!["A1:C3"]);
+    }
+
+    #[test]
+    fn windows_with_stride() {
+        let r = RangeOrCell::from_str("A1:E5").unwrap();
+
+        assert_eq!(
+            r.windows_with_stride(3, 3, 2, 2)
+                .unwrap()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>(),
+            vec!["A1:C3", "C1:E3", "A3:C5", "C3:E5"]
+        );
+        assert_eq!(
+            r.windows_with_stride(2, 5, 2, 1)
+                .unwrap()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>(),
+            vec!["A1:E2", "A3:E4"]
+        );
+    }
+
+    #[test]
+    fn windows_unbounded() {
+        assert_eq!(
+            RangeOrCell::from_str("B:B")
+                .unwrap()
+                .windows(7, 1)
+                .unwrap()
+                .take(2)
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>(),
+            vec!["B1:B7", "B2:B8"]
+        );
+    }
+
+    #[test]
+    fn windows_non_contiguous() {
+        assert_eq!(
+            windows_to_strs("A1:A3,C1:C2", 2, 1),
+            vec!["A1:A2", "A2:A3", "C1:C2"]
+        );
+    }
+
+    #[test]
+    fn windows_empty() {
+        let r = RangeOrCell::from_str("A1:B2").unwrap();
+
+        assert!(matches!(
+            r.windows(0, 1),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+        assert!(matches!(
+            r.windows(1, 0),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+        assert!(matches!(
+            r.windows_with_stride(1, 1, 0, 1),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+    }
+}
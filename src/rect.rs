@@ -0,0 +1,229 @@
+//! # Rect
+//!
+//! A crate-internal representation of the rectangle of cells covered by a contiguous reference.
+//! Unbounded edges (the bottom of a `ColumnRange` or the right side of a `RowRange`) are
+//! represented by `Index::MAX` so that everything can be treated as a plain rectangle.
+use crate::{Address, Index, RangeOrCell};
+use std::cmp;
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct Rect {
+    pub(crate) left: Index,
+    pub(crate) top: Index,
+    pub(crate) right: Index,
+    pub(crate) bottom: Index,
+}
+
+impl Rect {
+    /// How many cells the rectangle covers.  This is a (saturating) `u128` so that unbounded
+    /// rectangles don't overflow.
+    pub(crate) fn area(&self) -> u128 {
+        ((self.right - self.left) as u128 + 1).saturating_mul((self.bottom - self.top) as u128 + 1)
+    }
+
+    /// Is every cell of `self` in at least one of `rects`?
+    pub(crate) fn is_covered_by(&self, rects: &[Self]) -> bool {
+        let mut remaining = vec![*self];
+        for r in rects {
+            remaining = remaining
+                .iter()
+                .flat_map(|piece| piece.subtract(r))
+                .collect();
+            if remaining.is_empty() {
+                return true;
+            }
+        }
+
+        remaining.is_empty()
+    }
+
+    /// The (up to four) rectangles left over after cutting `other` out of `self`
+    pub(crate) fn subtract(&self, other: &Self) -> Vec<Self> {
+        if !self.intersects(other) {
+            return vec![*self];
+        }
+
+        let mut pieces = vec![];
+        // a band above and below `other` that span our full width
+        if other.top > self.top {
+            pieces.push(Self {
+                bottom: other.top - 1,
+                ..*self
+            });
+        }
+        if other.bottom < self.bottom {
+            pieces.push(Self {
+                top: other.bottom + 1,
+                ..*self
+            });
+        }
+
+        // and then whatever is left to the left and right of it
+        let top = cmp::max(self.top, other.top);
+        let bottom = cmp::min(self.bottom, other.bottom);
+        if other.left > self.left {
+            pieces.push(Self {
+                top,
+                bottom,
+                right: other.left - 1,
+                ..*self
+            });
+        }
+        if other.right < self.right {
+            pieces.push(Self {
+                top,
+                bottom,
+                left: other.right + 1,
+                ..*self
+            });
+        }
+
+        pieces
+    }
+
+    pub(crate) fn contains(&self, other: &Self) -> bool {
+        self.left <= other.left
+            && self.top <= other.top
+            && self.right >= other.right
+            && self.bottom >= other.bottom
+    }
+
+    pub(crate) fn intersects(&self, other: &Self) -> bool {
+        self.left <= other.right
+            && other.left <= self.right
+            && self.top <= other.bottom
+            && other.top <= self.bottom
+    }
+
+    /// The cells in both `self` and `other`, if there are any
+    pub(crate) fn intersection(&self, other: &Self) -> Option<Self> {
+        self.intersects(other).then(|| Self {
+            left: cmp::max(self.left, other.left),
+            top: cmp::max(self.top, other.top),
+            right: cmp::min(self.right, other.right),
+            bottom: cmp::min(self.bottom, other.bottom),
+        })
+    }
+
+    /// The smallest rectangle covering both `self` and `other`
+    pub(crate) fn union(&self, other: &Self) -> Self {
+        Self {
+            left: cmp::min(self.left, other.left),
+            top: cmp::min(self.top, other.top),
+            right: cmp::max(self.right, other.right),
+            bottom: cmp::max(self.bottom, other.bottom),
+        }
+    }
+}
+
+impl From<&Address> for Rect {
+    fn from(a: &Address) -> Self {
+        Self {
+            left: a.column.x,
+            top: a.row.y,
+            right: a.column.x,
+            bottom: a.row.y,
+        }
+    }
+}
+
+impl RangeOrCell {
+    /// Do `self` and `other` cover exactly the same cells?
+    pub(crate) fn covers_same_cells(&self, other: &Self) -> bool {
+        let (rects, other_rects) = (self.rects(), other.rects());
+
+        rects.iter().all(|r| r.is_covered_by(&other_rects))
+            && other_rects.iter().all(|r| r.is_covered_by(&rects))
+    }
+
+    /// The rectangles covered by the reference - one for each member of a `NonContiguous`,
+    /// otherwise just one.
+    pub(crate) fn rects(&self) -> Vec<Rect> {
+        let mut rects = vec![];
+        self.push_rects(&mut rects);
+        rects
+    }
+
+    fn push_rects(&self, rects: &mut Vec<Rect>) {
+        match self {
+            Self::Cell(a) => rects.push(a.into()),
+
+            Self::ColumnRange { from, to } => rects.push(Rect {
+                left: cmp::min(from.x, to.x),
+                top: 0,
+                right: cmp::max(from.x, to.x),
+                bottom: Index::MAX,
+            }),
+
+            Self::NonContiguous(range_or_cells) => {
+                for r in range_or_cells {
+                    r.push_rects(rects);
+                }
+            }
+
+            Self::Range { from, to } => {
+                let (top_left, bottom_right) = Address::corners(from, to);
+                rects.push(Rect::from(&top_left).union(&Rect::from(&bottom_right)));
+            }
+
+            Self::RowRange { from, to } => rects.push(Rect {
+                left: 0,
+                top: cmp::min(from.y, to.y),
+                right: Index::MAX,
+                bottom: cmp::max(from.y, to.y),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: Index, top: Index, right: Index, bottom: Index) -> Rect {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    #[test]
+    fn subtract() {
+        // cutting the middle out of a 3x3 leaves a band above, below, left and right
+        assert_eq!(
+            rect(0, 0, 2, 2).subtract(&rect(1, 1, 1, 1)),
+            vec![
+                rect(0, 0, 2, 0),
+                rect(0, 2, 2, 2),
+                rect(0, 1, 0, 1),
+                rect(2, 1, 2, 1)
+            ]
+        );
+        assert!(rect(1, 1, 1, 1).subtract(&rect(0, 0, 2, 2)).is_empty());
+        assert_eq!(
+            rect(0, 0, 1, 1).subtract(&rect(5, 5, 6, 6)),
+            vec![rect(0, 0, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn intersection() {
+        assert_eq!(
+            rect(0, 0, 5, 5).intersection(&rect(3, 4, 10, 10)),
+            Some(rect(3, 4, 5, 5))
+        );
+        assert_eq!(rect(0, 0, 5, 5).intersection(&rect(6, 0, 10, 10)), None);
+    }
+
+    #[test]
+    fn is_covered_by() {
+        let left = rect(0, 0, 0, 9);
+        let right = rect(1, 0, 1, 9);
+
+        assert!(rect(0, 0, 1, 9).is_covered_by(&[left, right]));
+        assert!(!rect(0, 0, 2, 9).is_covered_by(&[left, right]));
+        assert!(!rect(0, 0, 0, 0).is_covered_by(&[]));
+    }
+}
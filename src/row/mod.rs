@@ -1,5 +1,5 @@
 //! # Row
-use crate::Index;
+use crate::{Address, Column, Index};
 use std::cmp;
 
 mod as_ref;
@@ -10,19 +10,12 @@ mod into;
 mod ord;
 mod partial_ord;
 
-// need to implement this here in order to #[derive(Eq)] below
-impl PartialEq for Row {
-    fn eq(&self, other: &Self) -> bool {
-        self.y == other.y
-    }
-}
-
 #[cfg_attr(
     feature = "rkyv",
     derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize)
 )]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Copy, Clone, Debug, Eq)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Row {
     /// If the row was was specified with a `$`.
     pub absolute: bool,
@@ -40,12 +33,15 @@ impl Row {
 
     /// Is `self` (inclusively) between the given `a` and `b` rows
     pub fn is_between<T: AsRef<Self>>(&self, a: T, b: T) -> bool {
-        let a_ref = a.as_ref();
-        let b_ref = b.as_ref();
-        let from = cmp::min(a_ref, b_ref);
-        let to = cmp::max(a_ref, b_ref);
+        let (a_y, b_y) = (a.as_ref().y, b.as_ref().y);
+
+        self.y >= cmp::min(a_y, b_y) && self.y <= cmp::max(a_y, b_y)
+    }
 
-        self >= from && self <= to
+    /// Toggle between `1` and `$1`.  A row on its own only has the one flag, so this is the
+    /// whole of F4's cycle.
+    pub fn cycle_absolute(&self) -> Self {
+        self.with_absolute(!self.absolute)
     }
 
     /// Create a new `Row` (with `absolute`: `false`)
@@ -53,6 +49,15 @@ impl Row {
         Self { absolute: false, y }
     }
 
+    /// Move the row by a signed number of `rows` (negative is up).  Returns `None` if that would
+    /// move it above row 1.
+    pub fn offset(&self, rows: isize) -> Option<Self> {
+        Some(Self {
+            y: self.y.checked_add_signed(rows)?,
+            ..*self
+        })
+    }
+
     /// Shift the row down by the given amount.
     pub fn shift_down(&self, rows: Index) -> Self {
         if rows == 0 {
@@ -77,6 +82,32 @@ impl Row {
         }
     }
 
+    /// Flip the row into a column with the same index (and `absolute` flag), as if the grid were
+    /// transposed around `A1`.
+    pub fn transpose(&self) -> Column {
+        Column {
+            absolute: self.absolute,
+            x: self.y,
+        }
+    }
+
+    /// Flip the row into the column it lands on when the grid is transposed around `anchor`.
+    /// Returns `None` if that would be to the left of column A.
+    pub fn transpose_around(&self, anchor: &Address) -> Option<Column> {
+        Some(Column {
+            absolute: self.absolute,
+            x: self
+                .y
+                .checked_add(anchor.column.x)?
+                .checked_sub(anchor.row.y)?,
+        })
+    }
+
+    /// Set whether the row is `absolute` (`$1`) and return a `Copy`ed `Row`
+    pub fn with_absolute(&self, absolute: bool) -> Self {
+        Self { absolute, ..*self }
+    }
+
     /// Set the `y` and return a `Copy`ed `Row`
     pub fn with_y(&self, y: Index) -> Self {
         Self { y, ..*self }
@@ -87,6 +118,14 @@ impl Row {
 mod tests {
     use crate::*;
 
+    #[test]
+    fn cycle_absolute() {
+        let one = Row::new(0);
+
+        assert_eq!(one.cycle_absolute().to_string(), "$1");
+        assert_eq!(one.cycle_absolute().cycle_absolute(), one);
+    }
+
     #[test]
     fn contains_false() {
         assert!(!Row::new(0).contains(Row::new(1)));
@@ -94,6 +133,12 @@ mod tests {
         assert!(!Row::new(0).contains(Address::new(100, 10)));
     }
 
+    #[test]
+    fn with_absolute() {
+        assert_eq!(Row::new(1).with_absolute(true).to_string(), "$2");
+        assert_eq!(Row::new(1).with_absolute(false).to_string(), "2");
+    }
+
     #[test]
     fn contains_true() {
         assert!(Row::new(0).contains(Row::new(0)));
@@ -101,10 +146,28 @@ mod tests {
         assert!(Row::new(0).contains(Address::new(10, 0)));
     }
 
+    #[test]
+    fn eq() {
+        assert_eq!(Row::new(5), Row::new(5));
+        assert_ne!(Row::new(5), Row::new(6));
+        assert_ne!(
+            Row::new(5),
+            Row {
+                absolute: true,
+                y: 5
+            }
+        );
+    }
+
     #[test]
     fn is_between_true() {
         assert!(Row::new(5).is_between(&Row::new(0), &Row::new(20)));
         assert!(Row::new(5).is_between(&Row::new(5), &Row::new(20)));
+        assert!(Row {
+            absolute: true,
+            y: 5
+        }
+        .is_between(&Row::new(0), &Row::new(5)));
     }
 
     #[test]
@@ -112,6 +175,33 @@ mod tests {
         assert!(!Row::new(5).is_between(&Row::new(0), &Row::new(2)));
     }
 
+    #[test]
+    fn offset() {
+        assert_eq!(Row::new(5).offset(3), Some(Row::new(8)));
+        assert_eq!(Row::new(5).offset(-5), Some(Row::new(0)));
+        assert_eq!(Row::new(5).offset(-6), None);
+    }
+
+    #[test]
+    fn transpose() {
+        let column = Row {
+            absolute: true,
+            y: 3,
+        }
+        .transpose();
+        assert_eq!(column.x, 3);
+        assert!(column.absolute);
+    }
+
+    #[test]
+    fn transpose_around() {
+        assert_eq!(
+            Row::new(6).transpose_around(&Address::new(2, 5)),
+            Some(Column::new(3))
+        );
+        assert_eq!(Row::new(0).transpose_around(&Address::new(1, 2)), None);
+    }
+
     #[test]
     fn shift_down() {
         assert_eq!(Row::new(0).shift_down(1), Row::new(1));
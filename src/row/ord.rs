@@ -3,7 +3,10 @@ use std::cmp;
 
 impl Ord for Row {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
-        self.y.cmp(&other.y)
+        // `absolute` only breaks ties so that `Ord` agrees with `Eq`
+        self.y
+            .cmp(&other.y)
+            .then_with(|| self.absolute.cmp(&other.absolute))
     }
 }
 
@@ -16,5 +19,12 @@ mod tests {
         assert_eq!(Row::new(20).cmp(&Row::new(20)), cmp::Ordering::Equal);
         assert_eq!(Row::new(20).cmp(&Row::new(19)), cmp::Ordering::Greater);
         assert_eq!(Row::new(20).cmp(&Row::new(21)), cmp::Ordering::Less);
+        assert_eq!(
+            Row::new(20).cmp(&Row {
+                absolute: true,
+                y: 20
+            }),
+            cmp::Ordering::Less
+        );
     }
 }
@@ -0,0 +1,152 @@
+//! # Sheet names
+//!
+//! Quoting sheet names the way spreadsheets expect them when they prefix a reference: wrapped in
+//! single quotes with any `'` doubled up, but only when the name would otherwise be ambiguous.
+use std::borrow::Cow;
+
+/// Does the name need to be wrapped in quotes to be used as a prefix?  That's the case if it has
+/// anything besides letters, digits, `_` and `.` in it, starts with a digit or could be mistaken
+/// for a reference itself (like a sheet named `A1` or `R1C1`).
+pub(crate) fn needs_quotes(sheet_name: &str) -> bool {
+    let Some(first) = sheet_name.chars().next() else {
+        return true;
+    };
+
+    first.is_ascii_digit()
+        || sheet_name
+            .chars()
+            .any(|c| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        || is_a1(sheet_name)
+        || is_r1c1(sheet_name)
+}
+
+/// Does it look like a cell on a spreadsheet (one that goes up to column `XFD`) such as `A1` or
+/// `XFD1048576`?
+fn is_a1(s: &str) -> bool {
+    let letters = s.chars().take_while(|c| c.is_ascii_alphabetic()).count();
+    let (column, row) = s.split_at(letters);
+    let column = column.to_ascii_uppercase();
+
+    (1..=3).contains(&letters)
+        && (letters < 3 || column.as_str() <= "XFD")
+        && !row.is_empty()
+        && row.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Does it look like an R1C1-style reference such as `R1C1`, `R2` or `C`?
+fn is_r1c1(s: &str) -> bool {
+    let s = s.to_ascii_uppercase();
+    let rest = s
+        .strip_prefix('R')
+        .map(|r| r.trim_start_matches(|c: char| c.is_ascii_digit()));
+
+    match rest {
+        Some("") => true,
+        Some(rest) => rest
+            .strip_prefix('C')
+            .is_some_and(|c| c.chars().all(|c| c.is_ascii_digit())),
+        None => s
+            .strip_prefix('C')
+            .is_some_and(|c| c.chars().all(|c| c.is_ascii_digit())),
+    }
+}
+
+/// Are `a` and `b` the same sheet?  Like spreadsheets, sheet names are case-insensitive.
+pub(crate) fn is_same(a: &str, b: &str) -> bool {
+    a == b || folded(a) == folded(b)
+}
+
+/// The name with its case folded, for keying things by sheet so that names which are the same
+/// sheet (see `is_same`) have the same key
+pub(crate) fn folded(sheet_name: &str) -> String {
+    sheet_name.to_lowercase()
+}
+
+/// Are references with the (optional) sheet names `a` and `b` on the same sheet?  Like `is_same`,
+/// except that a reference without a sheet name is only ever on the same sheet as another one
+/// without.
+pub(crate) fn is_same_sheet(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => is_same(a, b),
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+/// The name as it should be written before the `!`
+pub(crate) fn quote(sheet_name: &str) -> Cow<'_, str> {
+    if needs_quotes(sheet_name) {
+        Cow::Owned(format!("'{}'", sheet_name.replace('\'', "''")))
+    } else {
+        Cow::Borrowed(sheet_name)
+    }
+}
+
+/// A span of sheets (`First:Last`) as it should be written before the `!`.  Both names go inside
+/// of the same pair of quotes.
+pub(crate) fn quote_span(first: &str, last: &str) -> String {
+    if needs_quotes(first) || needs_quotes(last) {
+        format!(
+            "'{}:{}'",
+            first.replace('\'', "''"),
+            last.replace('\'', "''")
+        )
+    } else {
+        format!("{first}:{last}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_plain() {
+        assert_eq!(quote("Sheet1"), "Sheet1");
+        assert_eq!(quote("Q1_2024.v2"), "Q1_2024.v2");
+        assert_eq!(quote("Übersicht"), "Übersicht");
+    }
+
+    #[test]
+    fn quote_special_chars() {
+        assert_eq!(quote("Q1 2024"), "'Q1 2024'");
+        assert_eq!(quote("Foo's Bar"), "'Foo''s Bar'");
+        assert_eq!(quote("a-b"), "'a-b'");
+        assert_eq!(quote(""), "''");
+    }
+
+    #[test]
+    fn quote_looks_like_a_reference() {
+        assert_eq!(quote("2024"), "'2024'");
+        assert_eq!(quote("A1"), "'A1'");
+        assert_eq!(quote("xfd2"), "'xfd2'");
+        assert_eq!(quote("XFE2"), "XFE2");
+        assert_eq!(quote("Sheet1"), "Sheet1");
+        assert_eq!(quote("R1C1"), "'R1C1'");
+        assert_eq!(quote("r2"), "'r2'");
+        assert_eq!(quote("C"), "'C'");
+        assert_eq!(quote("Report"), "Report");
+        assert_eq!(quote("Costs"), "Costs");
+    }
+
+    #[test]
+    fn is_same() {
+        assert!(super::is_same("Q1", "Q1"));
+        assert!(super::is_same("q1", "Q1"));
+        assert!(super::is_same("Ärger", "äRGER"));
+        assert!(!super::is_same("Q1", "Q10"));
+    }
+
+    #[test]
+    fn is_same_sheet() {
+        assert!(super::is_same_sheet(Some("Data"), Some("data")));
+        assert!(super::is_same_sheet(None, None));
+        assert!(!super::is_same_sheet(Some("Data"), None));
+        assert!(!super::is_same_sheet(None, Some("Data")));
+    }
+
+    #[test]
+    fn quote_span() {
+        assert_eq!(super::quote_span("Jan", "Mar"), "Jan:Mar");
+        assert_eq!(super::quote_span("Jan", "Mar 2"), "'Jan:Mar 2'");
+    }
+}
@@ -0,0 +1,404 @@
+//! # SparseGrid
+//!
+//! A sheet's worth of values keyed by `Address`, where only the cells that have something in
+//! them take up any space.  Cells are stored in row-major order, so reading a range walks just
+//! the populated cells inside of it (skipping ahead past the rows and columns outside of it)
+//! rather than every cell the range covers.
+//!
+//! ```
+//! use a1_notation::{Address, RangeOrCell, SparseGrid};
+//! use std::str::FromStr;
+//!
+//! let mut grid = SparseGrid::new();
+//! grid.set(Address::new(1, 1), 10);
+//! grid.set_a1("C5", 20).unwrap();
+//! grid.set_a1("Z100", 30).unwrap();
+//!
+//! let range = RangeOrCell::from_str("A1:C10").unwrap();
+//! let sum: i32 = grid.range(&range).map(|(_, v)| v).sum();
+//! assert_eq!(sum, 30);
+//!
+//! assert_eq!(grid.used_range().unwrap().to_string(), "B2:Z100");
+//! ```
+use crate::range_or_cell::cell_or_range;
+use crate::rect::Rect;
+use crate::{Address, Error, Index, RangeOrCell, Result, A1};
+use std::collections::{btree_map, BTreeMap};
+use std::{iter, str::FromStr};
+
+/// Values keyed by `Address`, stored sparsely and in row-major order.  Addresses are only used
+/// for their position - `$B$2` and `B2` are the same cell.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SparseGrid<T> {
+    /// Keyed by `(row, column)` so that they're ordered row-by-row
+    cells: BTreeMap<(Index, Index), T>,
+    /// How many cells are populated in each column, for working out the used range
+    columns: BTreeMap<Index, usize>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: BTreeMap::new(),
+            columns: BTreeMap::new(),
+        }
+    }
+}
+
+/// Parse `a1` as a single cell (with or without a sheet name, which is ignored)
+pub(crate) fn parse_cell(a1: &str) -> Result<Address> {
+    match A1::from_str(a1)?.reference {
+        RangeOrCell::Cell(address) => Ok(address),
+        _ => Err(Error::parse_error(a1, "Expected a single cell")),
+    }
+}
+
+fn key(address: &Address) -> (Index, Index) {
+    (address.row.y, address.column.x)
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// How many cells have a value
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&T> {
+        self.cells.get(&key(address))
+    }
+
+    pub fn get_mut(&mut self, address: &Address) -> Option<&mut T> {
+        self.cells.get_mut(&key(address))
+    }
+
+    /// Like `get` but for a cell written in A1 notation.
+    ///
+    /// Returns an `Error::A1ParseError` if `a1` isn't a single cell.
+    pub fn get_a1(&self, a1: &str) -> Result<Option<&T>> {
+        Ok(self.get(&parse_cell(a1)?))
+    }
+
+    /// Set the value of the cell at `address`, returning what was there before.
+    pub fn set(&mut self, address: Address, value: T) -> Option<T> {
+        let previous = self.cells.insert(key(&address), value);
+        if previous.is_none() {
+            *self.columns.entry(address.column.x).or_default() += 1;
+        }
+        previous
+    }
+
+    /// Like `set` but for a cell written in A1 notation.
+    ///
+    /// Returns an `Error::A1ParseError` if `a1` isn't a single cell.
+    pub fn set_a1(&mut self, a1: &str, value: T) -> Result<Option<T>> {
+        Ok(self.set(parse_cell(a1)?, value))
+    }
+
+    /// Empty the cell at `address`, returning what was in it.
+    pub fn remove(&mut self, address: &Address) -> Option<T> {
+        let removed = self.cells.remove(&key(address))?;
+
+        if let btree_map::Entry::Occupied(mut count) = self.columns.entry(address.column.x) {
+            *count.get_mut() -= 1;
+            if *count.get() == 0 {
+                count.remove();
+            }
+        }
+
+        Some(removed)
+    }
+
+    /// Empty every cell.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.columns.clear();
+    }
+
+    /// Every populated cell, row-by-row.
+    pub fn iter(&self) -> impl Iterator<Item = (Address, &T)> {
+        self.cells
+            .iter()
+            .map(|(&(y, x), value)| (Address::new(x, y), value))
+    }
+
+    /// The populated cells of `range_or_cell`, row-by-row (and each only once, even if the
+    /// members of a `NonContiguous` overlap).
+    pub fn range(&self, range_or_cell: &RangeOrCell) -> SparseGridRange<'_, T> {
+        let rects = range_or_cell.rects();
+        let bounds = rects.iter().copied().reduce(|a, b| a.union(&b));
+
+        SparseGridRange {
+            cells: &self.cells,
+            next: bounds.map(|b| (b.top, b.left)),
+            bounds,
+            rects,
+        }
+    }
+
+    /// The smallest range covering every populated cell (or `None` if there aren't any),
+    /// like a spreadsheet's "used range".
+    pub fn used_range(&self) -> Option<RangeOrCell> {
+        let (&(top, _), _) = self.cells.first_key_value()?;
+        let (&(bottom, _), _) = self.cells.last_key_value()?;
+        let (&left, _) = self.columns.first_key_value()?;
+        let (&right, _) = self.columns.last_key_value()?;
+
+        Some(cell_or_range(
+            Address::new(left, top),
+            Address::new(right, bottom),
+        ))
+    }
+
+    /// Empty every cell in `range_or_cell`, returning how many had a value.
+    pub fn clear_range(&mut self, range_or_cell: &RangeOrCell) -> usize {
+        let addresses = self
+            .range(range_or_cell)
+            .map(|(a, _)| a)
+            .collect::<Vec<_>>();
+        for address in &addresses {
+            self.remove(address);
+        }
+
+        addresses.len()
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Set every cell of `range_or_cell` to `value`.
+    ///
+    /// Returns an `Error::InvalidArgumentError` for a `ColumnRange` or `RowRange` (or anything
+    /// with one in it), which would fill every row or column of the grid.
+    pub fn fill(&mut self, range_or_cell: &RangeOrCell, value: T) -> Result<()> {
+        if range_or_cell
+            .rects()
+            .iter()
+            .any(|r| r.right == Index::MAX || r.bottom == Index::MAX)
+        {
+            return Err(Error::invalid_argument(
+                range_or_cell,
+                "Can not fill an unbounded reference",
+            ));
+        }
+
+        for address in range_or_cell.unique_addresses() {
+            self.set(address, value.clone());
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> FromIterator<(Address, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Address, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (address, value) in iter {
+            grid.set(address, value);
+        }
+        grid
+    }
+}
+
+/// The populated cells of a reference, as returned by `SparseGrid::range`.
+#[derive(Clone, Debug)]
+pub struct SparseGridRange<'a, T> {
+    cells: &'a BTreeMap<(Index, Index), T>,
+    rects: Vec<Rect>,
+    bounds: Option<Rect>,
+    /// Where to look from next, as a `(row, column)` key
+    next: Option<(Index, Index)>,
+}
+
+impl<T> SparseGridRange<'_, T> {
+    /// Where to look next after finding a cell at `(y, x)` which isn't in any of the members:
+    /// wherever the next member starts further along the row, or otherwise the next row.
+    fn skip_from(&self, bounds: &Rect, y: Index, x: Index) -> Option<(Index, Index)> {
+        let in_row = self
+            .rects
+            .iter()
+            .filter(|r| r.top <= y && y <= r.bottom && r.right >= x)
+            .map(|r| r.left.max(x))
+            .min();
+
+        match in_row {
+            Some(x) => Some((y, x)),
+            None if y < bounds.bottom => Some((y + 1, bounds.left)),
+            None => None,
+        }
+    }
+}
+
+impl<'a, T> iter::Iterator for SparseGridRange<'a, T> {
+    type Item = (Address, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bounds = self.bounds?;
+
+        loop {
+            let from = self.next?;
+            let (&(y, x), value) = self.cells.range(from..).next()?;
+            if y > bounds.bottom {
+                self.next = None;
+                return None;
+            }
+
+            if self
+                .rects
+                .iter()
+                .any(|r| r.top <= y && y <= r.bottom && r.left <= x && x <= r.right)
+            {
+                self.next = match x.checked_add(1) {
+                    Some(x) => Some((y, x)),
+                    None => y.checked_add(1).map(|y| (y, bounds.left)),
+                };
+                return Some((Address::new(x, y), value));
+            }
+
+            // nothing in the grid comes between `from` and here, so carry on from here
+            self.next = self.skip_from(&bounds, y, x);
+        }
+    }
+}
+
+impl<T> iter::FusedIterator for SparseGridRange<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::str::FromStr;
+
+    fn r(s: &str) -> RangeOrCell {
+        RangeOrCell::from_str(s).unwrap()
+    }
+
+    fn grid() -> SparseGrid<usize> {
+        ["A1", "C1", "B2", "D3", "B4", "A10", "Z10"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, a)| (Address::from_str(a).unwrap(), i))
+            .collect()
+    }
+
+    fn range_to_strs(grid: &SparseGrid<usize>, range: &str) -> Vec<String> {
+        grid.range(&r(range)).map(|(a, _)| a.to_string()).collect()
+    }
+
+    #[test]
+    fn get_and_set() {
+        let mut grid = SparseGrid::new();
+
+        assert_eq!(grid.set(Address::new(1, 1), "a"), None);
+        assert_eq!(grid.set_a1("$B$2", "b").unwrap(), Some("a"));
+        assert_eq!(grid.get(&Address::new(1, 1)), Some(&"b"));
+        assert_eq!(grid.get_a1("Sheet1!B2").unwrap(), Some(&"b"));
+        assert_eq!(grid.get_a1("C3").unwrap(), None);
+        assert!(grid.get_a1("A1:B2").is_err());
+        assert!(grid.set_a1("foo", "c").is_err());
+        assert_eq!(grid.len(), 1);
+    }
+
+    #[test]
+    fn remove() {
+        let mut grid = grid();
+
+        assert_eq!(grid.remove(&Address::new(25, 9)), Some(6));
+        assert_eq!(grid.remove(&Address::new(25, 9)), None);
+        assert_eq!(grid.used_range(), Some(r("A1:D10")));
+    }
+
+    #[test]
+    fn iter() {
+        assert_eq!(
+            grid()
+                .iter()
+                .map(|(a, _)| a.to_string())
+                .collect::<Vec<_>>(),
+            vec!["A1", "C1", "B2", "D3", "B4", "A10", "Z10"]
+        );
+    }
+
+    #[test]
+    fn range() {
+        let grid = grid();
+
+        assert_eq!(range_to_strs(&grid, "B1:C4"), vec!["C1", "B2", "B4"]);
+        assert_eq!(range_to_strs(&grid, "C4:B1"), vec!["C1", "B2", "B4"]);
+        assert_eq!(range_to_strs(&grid, "A:A"), vec!["A1", "A10"]);
+        assert_eq!(range_to_strs(&grid, "10:10"), vec!["A10", "Z10"]);
+        assert_eq!(range_to_strs(&grid, "D3"), vec!["D3"]);
+        assert!(range_to_strs(&grid, "E5:Y9").is_empty());
+    }
+
+    #[test]
+    fn range_non_contiguous() {
+        let grid = grid();
+
+        // row-major across the members, and only once where they overlap
+        assert_eq!(
+            range_to_strs(&grid, "B2:D4,A1:B2,Z:Z"),
+            vec!["A1", "B2", "D3", "B4", "Z10"]
+        );
+    }
+
+    #[test]
+    fn range_matches_filtering() {
+        let grid = (0..20)
+            .flat_map(|y| (0..20).map(move |x| (x, y)))
+            .filter(|(x, y)| (x * 7 + y * 3) % 5 == 0)
+            .map(|(x, y)| (Address::new(x, y), x + y))
+            .collect::<SparseGrid<_>>();
+
+        for range in ["C3:H9", "A1:T20", "B2:C3,F5:K6,D4", "E:F,3:3", "J10"] {
+            let range = r(range);
+            assert_eq!(
+                grid.range(&range).collect::<Vec<_>>(),
+                grid.iter()
+                    .filter(|(a, _)| range.contains(&RangeOrCell::Cell(*a)))
+                    .collect::<Vec<_>>(),
+                "{range}"
+            );
+        }
+    }
+
+    #[test]
+    fn used_range() {
+        assert_eq!(grid().used_range(), Some(r("A1:Z10")));
+        assert_eq!(SparseGrid::<usize>::new().used_range(), None);
+
+        let mut grid = SparseGrid::new();
+        grid.set(Address::new(3, 3), ());
+        assert_eq!(grid.used_range(), Some(r("D4")));
+    }
+
+    #[test]
+    fn clear_range() {
+        let mut grid = grid();
+
+        assert_eq!(grid.clear_range(&r("A:B")), 4);
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.used_range(), Some(r("C1:Z10")));
+
+        grid.clear();
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn fill() {
+        let mut grid = SparseGrid::new();
+
+        grid.fill(&r("A1:B2,B2:C2"), 1).unwrap();
+        assert_eq!(grid.len(), 5);
+        assert_eq!(grid.get_a1("C2").unwrap(), Some(&1));
+        assert!(matches!(
+            grid.fill(&r("A:A"), 1),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+    }
+}
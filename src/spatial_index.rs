@@ -0,0 +1,426 @@
+//! # SpatialIndex
+//!
+//! A collection of values keyed by `A1` references which can quickly answer "which references
+//! cover this cell?" (along with overlap and containment queries) without scanning every entry.
+//! Think validation rules, formats or protected ranges attached to parts of a spreadsheet.
+//!
+//! Each sheet gets its own R-tree of the rectangles covered by its references (and like
+//! spreadsheets, `Data` and `data` are the same sheet).  A
+//! `NonContiguous` reference is indexed by each of its members but is still a single entry.
+//!
+//! ```
+//! use a1_notation::{new, SpatialIndex};
+//!
+//! let mut index = SpatialIndex::new();
+//! index.insert(new("Sheet1!A1:C10").unwrap(), "header format");
+//! index.insert(new("Sheet1!B:B").unwrap(), "currency");
+//! index.insert(new("Sheet2!B2").unwrap(), "bold");
+//!
+//! let rules = index
+//!     .covering(&new("Sheet1!B2").unwrap())
+//!     .into_iter()
+//!     .map(|(_, v)| *v)
+//!     .collect::<Vec<_>>();
+//! assert_eq!(rules, vec!["header format", "currency"]);
+//! ```
+use crate::rect::Rect;
+use crate::sheet_name;
+use crate::A1;
+use std::collections::{BTreeMap, HashMap};
+use std::mem;
+
+/// The most children a node can have before it's split in two.
+const MAX_CHILDREN: usize = 8;
+
+#[derive(Clone, Debug)]
+enum Node {
+    Leaf(Vec<(Rect, usize)>),
+    Branch(Vec<(Rect, Node)>),
+}
+
+impl Node {
+    fn bounds(&self) -> Option<Rect> {
+        match self {
+            Self::Leaf(entries) => entries.iter().map(|(r, _)| *r).reduce(|a, b| a.union(&b)),
+            Self::Branch(children) => children.iter().map(|(r, _)| *r).reduce(|a, b| a.union(&b)),
+        }
+    }
+
+    /// Insert `rect`, returning a new sibling node if this one had to be split
+    fn insert(&mut self, rect: Rect, id: usize) -> Option<Node> {
+        match self {
+            Self::Leaf(entries) => {
+                entries.push((rect, id));
+                (entries.len() > MAX_CHILDREN).then(|| Self::Leaf(split(entries)))
+            }
+
+            Self::Branch(children) => {
+                // descend into whichever child needs to grow the least to fit `rect`
+                let (_, (child_rect, child)) = children
+                    .iter_mut()
+                    .enumerate()
+                    .min_by_key(|(_, (r, _))| (r.union(&rect).area() - r.area(), r.area()))?;
+
+                let sibling = child.insert(rect, id);
+                *child_rect = child_rect.union(&rect);
+
+                if let Some(sibling) = sibling {
+                    if let Some(child_bounds) = child.bounds() {
+                        *child_rect = child_bounds;
+                    }
+                    children.push((sibling.bounds()?, sibling));
+                }
+
+                (children.len() > MAX_CHILDREN).then(|| Self::Branch(split(children)))
+            }
+        }
+    }
+
+    /// Remove the entry for `id` at `rect`, returning if it was found
+    fn remove(&mut self, rect: &Rect, id: usize) -> bool {
+        match self {
+            Self::Leaf(entries) => {
+                let len = entries.len();
+                entries.retain(|(r, i)| !(*i == id && r == rect));
+                entries.len() != len
+            }
+
+            Self::Branch(children) => {
+                let mut removed = false;
+                for (child_rect, child) in children.iter_mut() {
+                    if child_rect.contains(rect) && child.remove(rect, id) {
+                        removed = true;
+                        break;
+                    }
+                }
+
+                if removed {
+                    // we don't rebalance - we just drop any nodes that became empty and shrink
+                    // the bounds of the rest
+                    children.retain_mut(|(child_rect, child)| match child.bounds() {
+                        Some(b) => {
+                            *child_rect = b;
+                            true
+                        }
+                        None => false,
+                    });
+                }
+
+                removed
+            }
+        }
+    }
+
+    fn search(&self, rect: &Rect, ids: &mut Vec<usize>) {
+        match self {
+            Self::Leaf(entries) => ids.extend(
+                entries
+                    .iter()
+                    .filter(|(r, _)| r.intersects(rect))
+                    .map(|(_, id)| *id),
+            ),
+
+            Self::Branch(children) => {
+                for (child_rect, child) in children {
+                    if child_rect.intersects(rect) {
+                        child.search(rect, ids);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Split `children` in half along whichever axis they're most spread out on, returning the
+/// second half.
+fn split<T>(children: &mut Vec<(Rect, T)>) -> Vec<(Rect, T)> {
+    let center = |r: &Rect| {
+        (
+            r.left as u128 + r.right as u128,
+            r.top as u128 + r.bottom as u128,
+        )
+    };
+
+    let spread = |axis: fn((u128, u128)) -> u128| {
+        let centers = children.iter().map(|(r, _)| axis(center(r)));
+        centers.clone().max().unwrap_or(0) - centers.min().unwrap_or(0)
+    };
+
+    if spread(|c| c.0) >= spread(|c| c.1) {
+        children.sort_by_key(|(r, _)| center(r).0);
+    } else {
+        children.sort_by_key(|(r, _)| center(r).1);
+    }
+
+    children.split_off(children.len() / 2)
+}
+
+#[derive(Clone, Debug)]
+struct RTree {
+    root: Node,
+}
+
+impl RTree {
+    fn remove(&mut self, rect: &Rect, id: usize) {
+        self.root.remove(rect, id);
+
+        // don't let the tree stay taller than it needs to be
+        while let Node::Branch(children) = &mut self.root {
+            if children.len() > 1 {
+                break;
+            }
+
+            self.root = children
+                .pop()
+                .map_or(Node::Leaf(vec![]), |(_, child)| child);
+        }
+    }
+
+    fn insert(&mut self, rect: Rect, id: usize) {
+        if let Some(sibling) = self.root.insert(rect, id) {
+            let old_root = mem::replace(&mut self.root, Node::Branch(vec![]));
+            if let (Some(a), Some(b)) = (old_root.bounds(), sibling.bounds()) {
+                self.root = Node::Branch(vec![(a, old_root), (b, sibling)]);
+            }
+        }
+    }
+}
+
+/// Values keyed by `A1` references, indexed so that point, overlap and containment queries
+/// don't need to look at every entry.  The same reference can be inserted more than once.
+#[derive(Clone, Debug)]
+pub struct SpatialIndex<T> {
+    entries: BTreeMap<usize, (A1, T)>,
+    next_id: usize,
+    sheets: HashMap<Option<String>, RTree>,
+}
+
+impl<T> Default for SpatialIndex<T> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            next_id: 0,
+            sheets: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SpatialIndex<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `value` under the reference `a1`
+    pub fn insert(&mut self, a1: A1, value: T) {
+        let rects = a1.reference.rects();
+        let tree = self.sheets.entry(sheet_key(&a1)).or_insert_with(|| RTree {
+            root: Node::Leaf(vec![]),
+        });
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        for rect in rects {
+            tree.insert(rect, id);
+        }
+        self.entries.insert(id, (a1, value));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// How many entries are in the index
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Remove every entry whose reference is equal to `a1`, returning their values.
+    pub fn remove(&mut self, a1: &A1) -> Vec<T> {
+        let rects = a1.reference.rects();
+        let mut removed = vec![];
+
+        for id in self.candidates(a1) {
+            if !matches!(self.entries.get(&id), Some((other, _)) if other == a1) {
+                continue;
+            }
+
+            if let Some(tree) = self.sheets.get_mut(&sheet_key(a1)) {
+                for rect in &rects {
+                    tree.remove(rect, id);
+                }
+            }
+
+            if let Some((_, value)) = self.entries.remove(&id) {
+                removed.push(value);
+            }
+        }
+
+        removed
+    }
+
+    /// Entries whose reference contains all of `a1`.  Pass a single cell to find everything
+    /// covering that cell.
+    pub fn covering(&self, a1: &A1) -> Vec<(&A1, &T)> {
+        self.query(a1, |entry| entry.contains(a1))
+    }
+
+    /// Entries whose reference has at least one cell in common with `a1`.
+    pub fn overlapping(&self, a1: &A1) -> Vec<(&A1, &T)> {
+        let rects = a1.reference.rects();
+        self.query(a1, |entry| {
+            entry
+                .reference
+                .rects()
+                .iter()
+                .any(|r| rects.iter().any(|o| r.intersects(o)))
+        })
+    }
+
+    /// Entries whose reference is completely inside `a1`.
+    pub fn within(&self, a1: &A1) -> Vec<(&A1, &T)> {
+        self.query(a1, |entry| a1.contains(entry))
+    }
+
+    /// The ids of every entry in the same sheet that might overlap `a1`, in insertion order.
+    fn candidates(&self, a1: &A1) -> Vec<usize> {
+        let mut ids = vec![];
+        if let Some(tree) = self.sheets.get(&sheet_key(a1)) {
+            for rect in a1.reference.rects() {
+                tree.root.search(&rect, &mut ids);
+            }
+        }
+
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    fn query<F: Fn(&A1) -> bool>(&self, a1: &A1, f: F) -> Vec<(&A1, &T)> {
+        self.candidates(a1)
+            .into_iter()
+            .filter_map(|id| self.entries.get(&id))
+            .filter(|(entry, _)| f(entry))
+            .map(|(entry, value)| (entry, value))
+            .collect()
+    }
+}
+
+/// Which tree `a1` goes in
+fn sheet_key(a1: &A1) -> Option<String> {
+    a1.sheet_name.as_deref().map(sheet_name::folded)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn values(found: Vec<(&A1, &usize)>) -> Vec<usize> {
+        found.into_iter().map(|(_, v)| *v).collect()
+    }
+
+    fn index() -> SpatialIndex<usize> {
+        let mut index = SpatialIndex::new();
+        index.insert(new("A1:C3").unwrap(), 0);
+        index.insert(new("B:B").unwrap(), 1);
+        index.insert(new("2:2").unwrap(), 2);
+        index.insert(new("E5,G7").unwrap(), 3);
+        index.insert(new("Foo!A1:C3").unwrap(), 4);
+        index
+    }
+
+    #[test]
+    fn covering() {
+        let index = index();
+
+        assert_eq!(values(index.covering(&new("B2").unwrap())), vec![0, 1, 2]);
+        assert_eq!(values(index.covering(&new("G7").unwrap())), vec![3]);
+        assert_eq!(values(index.covering(&new("Foo!B2").unwrap())), vec![4]);
+        assert_eq!(values(index.covering(&new("B1:B2").unwrap())), vec![0, 1]);
+        assert!(index.covering(&new("Bar!B2").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn sheet_names_case_insensitive() {
+        let mut index = index();
+        index.insert(new("FOO!B2").unwrap(), 5);
+
+        assert_eq!(values(index.covering(&new("foo!B2").unwrap())), vec![4, 5]);
+        assert_eq!(
+            values(index.overlapping(&new("fOO!C3:D4").unwrap())),
+            vec![4]
+        );
+        assert_eq!(
+            values(index.within(&new("foo!A1:Z26").unwrap())),
+            vec![4, 5]
+        );
+    }
+
+    #[test]
+    fn overlapping() {
+        let index = index();
+
+        assert_eq!(
+            values(index.overlapping(&new("C3:E5").unwrap())),
+            vec![0, 3]
+        );
+        assert_eq!(values(index.overlapping(&new("D:D").unwrap())), vec![2]);
+    }
+
+    #[test]
+    fn within() {
+        let index = index();
+
+        assert_eq!(values(index.within(&new("A1:D4").unwrap())), vec![0]);
+        assert_eq!(values(index.within(&new("Foo!A1:Z26").unwrap())), vec![4]);
+        assert!(index.within(&new("B2:C3").unwrap()).is_empty());
+        assert_eq!(values(index.within(&new("A1:H8").unwrap())), vec![0, 3]);
+    }
+
+    #[test]
+    fn remove() {
+        let mut index = index();
+        index.insert(new("B:B").unwrap(), 5);
+
+        assert_eq!(index.remove(&new("B:B").unwrap()), vec![1, 5]);
+        assert_eq!(index.len(), 4);
+        assert_eq!(values(index.covering(&new("B2").unwrap())), vec![0, 2]);
+
+        index.insert(new("B2").unwrap(), 6);
+        assert_eq!(values(index.covering(&new("B2").unwrap())), vec![0, 2, 6]);
+    }
+
+    #[test]
+    fn many_entries() {
+        let mut index = SpatialIndex::new();
+        for x in 0..50 {
+            for y in 0..50 {
+                index.insert(range((x, y), (x + 2, y + 2)), (x, y));
+            }
+        }
+
+        let mut found = index
+            .covering(&cell(10, 10))
+            .into_iter()
+            .map(|(_, v)| *v)
+            .collect::<Vec<_>>();
+        found.sort();
+
+        let mut expected = vec![];
+        for x in 8..=10 {
+            for y in 8..=10 {
+                expected.push((x, y));
+            }
+        }
+
+        assert_eq!(found, expected);
+
+        for x in 0..50 {
+            for y in 0..50 {
+                assert_eq!(index.remove(&range((x, y), (x + 2, y + 2))).len(), 1);
+            }
+        }
+        assert!(index.is_empty());
+        assert!(index.covering(&cell(10, 10)).is_empty());
+    }
+}
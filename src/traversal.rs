@@ -0,0 +1,47 @@
+//! # Traversal
+//!
+//! The order to visit the cells of a range in.  Orders are relative to the way the range is
+//! written - "row-major" starts at `from` and walks along its row towards `to`'s column, so
+//! `C3:A1` goes right-to-left and bottom-to-top.
+
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Traversal {
+    /// Row-by-row: `A1, B1, A2, B2`
+    #[default]
+    RowMajor,
+
+    /// Column-by-column: `A1, A2, B1, B2`
+    ColumnMajor,
+
+    /// `RowMajor` backwards: `B2, A2, B1, A1`
+    ReverseRowMajor,
+
+    /// `ColumnMajor` backwards: `B2, B1, A2, A1`
+    ReverseColumnMajor,
+
+    /// Row-by-row, turning around at the end of each row (boustrophedon): `A1, B1, B2, A2`
+    RowSerpentine,
+
+    /// Column-by-column, turning around at the end of each column: `A1, A2, B2, B1`
+    ColumnSerpentine,
+}
+
+impl Traversal {
+    /// Does this walk backwards (from `to` to `from`)?
+    pub fn is_reverse(&self) -> bool {
+        matches!(self, Self::ReverseRowMajor | Self::ReverseColumnMajor)
+    }
+
+    /// Does this go column-by-column?
+    pub(crate) fn is_column_major(&self) -> bool {
+        matches!(
+            self,
+            Self::ColumnMajor | Self::ReverseColumnMajor | Self::ColumnSerpentine
+        )
+    }
+
+    pub(crate) fn is_serpentine(&self) -> bool {
+        matches!(self, Self::RowSerpentine | Self::ColumnSerpentine)
+    }
+}
@@ -0,0 +1,452 @@
+//! # XlsxWriter
+//!
+//! Writing blocks of values, formulas and merged ranges into an xlsx workbook at `A1` anchors
+//! with [rust_xlsxwriter](https://docs.rs/rust_xlsxwriter) (behind the `rust_xlsxwriter`
+//! feature).  Sheets are created the first time they're written to and every area that's
+//! written is remembered, so anything that would overwrite an earlier block is an error rather
+//! than a silently clobbered report.
+//!
+//! ```
+//! use a1_notation::{new, XlsxWriter};
+//!
+//! let mut writer = XlsxWriter::new();
+//! let table = writer
+//!     .write_block(
+//!         &new("Data!A1").unwrap(),
+//!         vec![vec!["widget", "3"], vec!["gadget", "5"]],
+//!     )
+//!     .unwrap();
+//! assert_eq!(table.to_string(), "Data!A1:B2");
+//!
+//! writer
+//!     .merge_range(&new("Summary!B2:D2").unwrap(), "Totals")
+//!     .unwrap();
+//! writer
+//!     .write_formula(&new("Summary!B3").unwrap(), "=COUNTA(Data!A1:A2)")
+//!     .unwrap();
+//!
+//! // B2:D2 is already merged
+//! assert!(writer.write_block(&new("Summary!C2").unwrap(), vec![vec![1]]).is_err());
+//!
+//! let xlsx = writer.save_to_buffer().unwrap();
+//! ```
+use crate::range_or_cell::cell_or_range;
+use crate::sheet_name;
+use crate::{Address, Error, Index, RangeOrCell, Result, SpatialIndex, A1};
+use rust_xlsxwriter::{ColNum, Format, IntoExcelData, RowNum, Workbook, Worksheet};
+use std::{fmt, path};
+
+/// How many rows and columns an xlsx worksheet has
+const MAX_ROWS: Index = 1_048_576;
+const MAX_COLUMNS: Index = 16_384;
+
+/// An xlsx workbook which is written to by `A1` references.  See the module docs.
+#[derive(Default)]
+pub struct XlsxWriter {
+    workbook: Workbook,
+    written: SpatialIndex<()>,
+}
+
+/// `Workbook` isn't `Debug`, so this only shows what's been written.
+impl fmt::Debug for XlsxWriter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("XlsxWriter")
+            .field("written", &self.written)
+            .finish_non_exhaustive()
+    }
+}
+
+impl XlsxWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write a block of rows with its top-left at the cell `anchor`, returning the area it
+    /// covers.  Rows can be different lengths - the area is as wide as the longest one.
+    ///
+    /// Returns an `Error::OverlapError` if the area overlaps anything already written to the
+    /// sheet, an `Error::OutOfBoundsError` if it goes past the edge of the sheet and an
+    /// `Error::InvalidArgumentError` if `rows` is empty, `anchor` isn't a cell or its sheet name
+    /// isn't allowed in a workbook.
+    ///
+    /// A value that can't be written (like a string that's too long for a cell) is an
+    /// `Error::IoError`.  The values before it have already been written by then, so the whole
+    /// area is still recorded and can't be written over.  If nothing was written it isn't
+    /// recorded, so the same area can be written again.
+    pub fn write_block<R, I, T>(&mut self, anchor: &A1, rows: R) -> Result<A1>
+    where
+        R: IntoIterator<Item = I>,
+        I: IntoIterator<Item = T>,
+        T: IntoExcelData,
+    {
+        let RangeOrCell::Cell(from) = anchor.reference else {
+            return Err(Error::invalid_argument(anchor, "Expected a single cell"));
+        };
+
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if width == 0 {
+            return Err(Error::invalid_argument(anchor, "There is nothing to write"));
+        }
+
+        let to = Address::new(
+            from.column.x.saturating_add(width - 1),
+            from.row.y.saturating_add(rows.len() - 1),
+        );
+        let area = A1 {
+            sheet_name: anchor.sheet_name.clone(),
+            reference: cell_or_range(from, to),
+        };
+
+        let (worksheet, area) = self.claim(area)?;
+        let (top, left) = position(&from).expect("checked by claim");
+        let mut cells_written = 0;
+        let result = rows.into_iter().enumerate().try_for_each(|(y, row)| {
+            row.into_iter().enumerate().try_for_each(|(x, value)| {
+                worksheet
+                    .write(top + y as RowNum, left + x as ColNum, value)
+                    .map_err(Error::io_error)?;
+                cells_written += 1;
+                Ok(())
+            })
+        });
+
+        // anything written before a failure is in the workbook now, so it has to be protected
+        if cells_written > 0 {
+            self.written.insert(area.clone(), ());
+        }
+
+        result.map(|()| area)
+    }
+
+    /// Write `formula` (like `=SUM(A1:A10)`) into the cell `a1`.
+    ///
+    /// Returns an `Error::OverlapError` if the cell has already been written to and the same
+    /// errors as `write_block` otherwise.
+    pub fn write_formula(&mut self, a1: &A1, formula: &str) -> Result<()> {
+        let RangeOrCell::Cell(address) = a1.reference else {
+            return Err(Error::invalid_argument(a1, "Expected a single cell"));
+        };
+
+        let (worksheet, area) = self.claim(a1.clone())?;
+        let (row, column) = position(&address).expect("checked by claim");
+        worksheet
+            .write_formula(row, column, formula)
+            .map_err(Error::io_error)?;
+        self.written.insert(area, ());
+
+        Ok(())
+    }
+
+    /// Merge the cells of the `Range` `a1` into one with `value` in it.
+    ///
+    /// Returns an `Error::InvalidArgumentError` if `a1` isn't a `Range` and the same errors as
+    /// `write_block` otherwise.
+    pub fn merge_range(&mut self, a1: &A1, value: &str) -> Result<()> {
+        let RangeOrCell::Range { from, to } = a1.reference else {
+            return Err(Error::invalid_argument(a1, "Only a `Range` can be merged"));
+        };
+
+        let (worksheet, area) = self.claim(a1.clone())?;
+        let (from, to) = (
+            position(&from).expect("checked by claim"),
+            position(&to).expect("checked by claim"),
+        );
+        worksheet
+            .merge_range(
+                from.0.min(to.0),
+                from.1.min(to.1),
+                from.0.max(to.0),
+                from.1.max(to.1),
+                value,
+                &Format::new(),
+            )
+            .map_err(Error::io_error)?;
+        self.written.insert(area, ());
+
+        Ok(())
+    }
+
+    /// The underlying workbook, for things like formatting which aren't done by `A1` here.
+    /// Anything written to it directly isn't checked for overlaps.
+    pub fn workbook_mut(&mut self) -> &mut Workbook {
+        &mut self.workbook
+    }
+
+    pub fn into_workbook(self) -> Workbook {
+        self.workbook
+    }
+
+    /// Write the workbook out to an xlsx file.
+    pub fn save<P: AsRef<path::Path>>(&mut self, path: P) -> Result<()> {
+        self.workbook.save(path).map_err(Error::io_error)
+    }
+
+    /// Write the workbook out as the bytes of an xlsx file.
+    pub fn save_to_buffer(&mut self) -> Result<Vec<u8>> {
+        self.workbook.save_to_buffer().map_err(Error::io_error)
+    }
+
+    /// Make sure `area` is on the sheet and doesn't overlap anything written before.  Returns
+    /// the worksheet to write it to (which is created if it's not there yet) along with `area`
+    /// qualified by the worksheet's name, which the caller records in `written` once it's
+    /// actually been written.
+    fn claim(&mut self, area: A1) -> Result<(&mut Worksheet, A1)> {
+        let on_sheet = area
+            .reference
+            .rects()
+            .iter()
+            .all(|rect| rect.right < MAX_COLUMNS && rect.bottom < MAX_ROWS);
+        if !on_sheet {
+            return Err(Error::out_of_bounds(
+                &area,
+                "It goes past the edge of an xlsx worksheet",
+            ));
+        }
+
+        // like references, sheet names are case-insensitive - so `data!A1` goes on `Data`
+        let worksheets = self.workbook.worksheets();
+        let index = match &area.sheet_name {
+            Some(name) => worksheets
+                .iter()
+                .position(|w| sheet_name::is_same(&w.name(), name)),
+            None => (!worksheets.is_empty()).then_some(0),
+        };
+
+        let Some(index) = index else {
+            // a new sheet has nothing on it to overlap with.  it's named before it's added, so a
+            // bad name doesn't leave a sheet behind
+            let worksheet = match &area.sheet_name {
+                Some(name) => {
+                    let mut worksheet = Worksheet::new();
+                    worksheet
+                        .set_name(name)
+                        .map_err(|e| Error::invalid_argument(&area, e.to_string()))?;
+                    self.workbook.push_worksheet(worksheet);
+                    self.workbook
+                        .worksheets_mut()
+                        .last_mut()
+                        .expect("it was just pushed")
+                }
+                None => self.workbook.add_worksheet(),
+            };
+            let area = A1 {
+                sheet_name: Some(worksheet.name()),
+                ..area
+            };
+            return Ok((worksheet, area));
+        };
+
+        let worksheet = &mut self.workbook.worksheets_mut()[index];
+        let area = A1 {
+            sheet_name: Some(worksheet.name()),
+            ..area
+        };
+        if let Some((other, _)) = self.written.overlapping(&area).first() {
+            return Err(Error::overlap(&area, format!("It overlaps `{other}`")));
+        }
+
+        Ok((worksheet, area))
+    }
+}
+
+/// The zero-based `(row, column)` of `address` on an xlsx worksheet
+fn position(address: &Address) -> Option<(RowNum, ColNum)> {
+    Some((
+        RowNum::try_from(address.row.y).ok()?,
+        ColNum::try_from(address.column.x).ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn a1(s: &str) -> A1 {
+        new(s).unwrap()
+    }
+
+    #[test]
+    fn write_block() {
+        let mut writer = XlsxWriter::new();
+
+        assert_eq!(
+            writer
+                .write_block(&a1("Summary!B3"), vec![vec![1, 2, 3], vec![4]])
+                .unwrap(),
+            a1("Summary!B3:D4")
+        );
+        assert_eq!(
+            writer.write_block(&a1("C1"), vec![vec!["x"]]).unwrap(),
+            a1("Summary!C1")
+        );
+        assert!(writer.save_to_buffer().unwrap().starts_with(b"PK"));
+    }
+
+    #[test]
+    fn write_block_creates_sheets() {
+        let mut writer = XlsxWriter::new();
+        writer.write_block(&a1("A1"), vec![vec![1]]).unwrap();
+        writer.write_block(&a1("Data!A1"), vec![vec![1]]).unwrap();
+        writer
+            .write_block(&a1("'My Data'!A1"), vec![vec![1]])
+            .unwrap();
+
+        let mut workbook = writer.into_workbook();
+        assert_eq!(
+            workbook
+                .worksheets()
+                .iter()
+                .map(|w| w.name())
+                .collect::<Vec<_>>(),
+            vec!["Sheet1", "Data", "My Data"]
+        );
+    }
+
+    #[test]
+    fn overlaps() {
+        let mut writer = XlsxWriter::new();
+        writer
+            .write_block(&a1("Data!B2"), vec![vec![1, 2], vec![3, 4]])
+            .unwrap();
+        writer.merge_range(&a1("Data!D2:E3"), "merged").unwrap();
+
+        assert_eq!(
+            writer.write_block(&a1("Data!A1"), vec![vec![1, 2], vec![3, 4]]),
+            Err(Error::OverlapError {
+                reference: "Data!A1:B2".to_string(),
+                message: "It overlaps `Data!B2:C3`".to_string(),
+            })
+        );
+        assert!(matches!(
+            writer.write_formula(&a1("Data!E3"), "=1"),
+            Err(Error::OverlapError { .. })
+        ));
+        assert!(matches!(
+            writer.merge_range(&a1("Data!C1:D1"), "ok"),
+            Ok(())
+        ));
+
+        // a different sheet is fine
+        writer.write_formula(&a1("Other!B2"), "=1").unwrap();
+    }
+
+    #[test]
+    fn errors() {
+        let mut writer = XlsxWriter::new();
+
+        assert!(matches!(
+            writer.write_block(&a1("A1:B2"), vec![vec![1]]),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+        assert!(matches!(
+            writer.write_block(&a1("A1"), Vec::<Vec<i32>>::new()),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+        assert!(matches!(
+            writer.write_block(&a1("XFD1"), vec![vec![1, 2]]),
+            Err(Error::OutOfBoundsError { .. })
+        ));
+        assert!(matches!(
+            writer.write_formula(&a1("A1:B2"), "=1"),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+        assert!(matches!(
+            writer.merge_range(&a1("A:B"), "nope"),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+        assert!(matches!(
+            writer.write_block(&a1("'Bad [name]'!A1"), vec![vec![1]]),
+            Err(Error::InvalidArgumentError { .. })
+        ));
+
+        // nothing that failed was recorded as written (or left a sheet behind)
+        writer.write_block(&a1("A1"), vec![vec![1]]).unwrap();
+        assert_eq!(writer.into_workbook().worksheets().len(), 1);
+    }
+
+    #[test]
+    fn failed_write_is_not_recorded() {
+        let mut writer = XlsxWriter::new();
+        let too_long = "x".repeat(40_000);
+
+        assert!(matches!(
+            writer.write_block(&a1("Data!A1"), vec![vec![too_long.as_str()]]),
+            Err(Error::IoError { .. })
+        ));
+        assert!(matches!(
+            writer.merge_range(&a1("Data!B1:C1"), &too_long),
+            Err(Error::IoError { .. })
+        ));
+
+        writer
+            .write_block(&a1("Data!A1"), vec![vec!["ok"]])
+            .unwrap();
+        writer.merge_range(&a1("Data!B1:C1"), "ok").unwrap();
+    }
+
+    #[test]
+    fn failed_part_way_is_recorded() {
+        let mut writer = XlsxWriter::new();
+        let too_long = "x".repeat(40_000);
+
+        assert!(matches!(
+            writer.write_block(&a1("Data!A1"), vec![vec!["ok", too_long.as_str()]]),
+            Err(Error::IoError { .. })
+        ));
+        assert_eq!(
+            writer.write_formula(&a1("Data!A1"), "=1"),
+            Err(Error::OverlapError {
+                reference: "Data!A1".to_string(),
+                message: "It overlaps `Data!A1:B1`".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn sheet_names_case_insensitive() {
+        let mut writer = XlsxWriter::new();
+        writer.write_block(&a1("Data!A1"), vec![vec![1]]).unwrap();
+
+        assert_eq!(
+            writer.write_block(&a1("data!B1"), vec![vec![2]]).unwrap(),
+            a1("Data!B1")
+        );
+        assert_eq!(
+            writer.write_formula(&a1("DATA!A1"), "=1"),
+            Err(Error::OverlapError {
+                reference: "Data!A1".to_string(),
+                message: "It overlaps `Data!A1`".to_string(),
+            })
+        );
+        assert_eq!(writer.into_workbook().worksheets().len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "calamine")]
+    fn round_trip() {
+        use calamine::{open_workbook_auto_from_rs, Data};
+        use std::io::Cursor;
+
+        let mut writer = XlsxWriter::new();
+        writer
+            .write_block(&a1("Report!B3"), vec![vec!["a", "b"], vec!["c", "d"]])
+            .unwrap();
+        let mut workbook =
+            open_workbook_auto_from_rs(Cursor::new(writer.save_to_buffer().unwrap())).unwrap();
+
+        assert_eq!(
+            a1("Report!B3:C4")
+                .read_range(&mut workbook)
+                .unwrap()
+                .into_rows(),
+            vec![
+                vec![Data::String("a".to_string()), Data::String("b".to_string())],
+                vec![Data::String("c".to_string()), Data::String("d".to_string())],
+            ]
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Checks `RangeOrCell::contains` against a brute-force enumeration of the cells in every pair of
+//! a (small) exhaustive set of references.
+//!
+//! All of the bounded references fit in a 4x4 grid but we enumerate cells in a 6x6 one, so that
+//! the extra rows and columns of the `ColumnRange`s and `RowRange`s stand in for them being
+//! unbounded.
+use a1_notation::{Address, Column, RangeOrCell, Row};
+use std::collections::BTreeSet;
+
+const BOUNDED: usize = 4;
+const WINDOW: usize = 6;
+
+fn is_in(r: &RangeOrCell, x: usize, y: usize) -> bool {
+    let between = |v: usize, a: usize, b: usize| v >= a.min(b) && v <= a.max(b);
+
+    match r {
+        RangeOrCell::Cell(a) => a.column.x == x && a.row.y == y,
+        RangeOrCell::ColumnRange { from, to } => between(x, from.x, to.x),
+        RangeOrCell::NonContiguous(rs) => rs.iter().any(|r| is_in(r, x, y)),
+        RangeOrCell::Range { from, to } => {
+            between(x, from.column.x, to.column.x) && between(y, from.row.y, to.row.y)
+        }
+        RangeOrCell::RowRange { from, to } => between(y, from.y, to.y),
+    }
+}
+
+fn cells(r: &RangeOrCell) -> BTreeSet<(usize, usize)> {
+    let mut cells = BTreeSet::new();
+    for x in 0..WINDOW {
+        for y in 0..WINDOW {
+            if is_in(r, x, y) {
+                cells.insert((x, y));
+            }
+        }
+    }
+    cells
+}
+
+fn address(x: usize, y: usize) -> Address {
+    // sprinkle in some absolute flags, which should never make a difference
+    Address {
+        column: Column {
+            absolute: x.is_multiple_of(2),
+            x,
+        },
+        row: Row {
+            absolute: y.is_multiple_of(3),
+            y,
+        },
+    }
+}
+
+fn references() -> Vec<RangeOrCell> {
+    let mut refs = vec![];
+    let points = (0..BOUNDED).flat_map(|x| (0..BOUNDED).map(move |y| (x, y)));
+
+    for (x, y) in points.clone() {
+        refs.push(RangeOrCell::Cell(address(x, y)));
+    }
+
+    for (ax, ay) in points.clone() {
+        for (bx, by) in points.clone() {
+            refs.push(RangeOrCell::Range {
+                from: address(ax, ay),
+                to: address(bx, by),
+            });
+        }
+    }
+
+    for a in 0..BOUNDED {
+        for b in 0..BOUNDED {
+            refs.push(RangeOrCell::column_range(a, b));
+            refs.push(RangeOrCell::row_range(a, b));
+        }
+    }
+
+    let members = [
+        RangeOrCell::Cell(address(0, 0)),
+        RangeOrCell::Cell(address(1, 0)),
+        RangeOrCell::Cell(address(2, 2)),
+        RangeOrCell::range((0, 0), (0, 3)),
+        RangeOrCell::range((1, 0), (1, 3)),
+        RangeOrCell::range((0, 0), (1, 1)),
+        RangeOrCell::range((2, 1), (3, 3)),
+        RangeOrCell::column(1),
+        RangeOrCell::column_range(2, 3),
+        RangeOrCell::row(0),
+        RangeOrCell::row_range(1, 3),
+    ];
+
+    refs.push(RangeOrCell::NonContiguous(vec![]));
+    for a in &members {
+        for b in &members {
+            refs.push(RangeOrCell::NonContiguous(vec![a.clone(), b.clone()]));
+        }
+    }
+    refs.push(RangeOrCell::NonContiguous(members.to_vec()));
+
+    refs
+}
+
+#[test]
+fn contains_matches_brute_force() {
+    let refs = references()
+        .into_iter()
+        .map(|r| {
+            let c = cells(&r);
+            (r, c)
+        })
+        .collect::<Vec<_>>();
+
+    for (a, a_cells) in &refs {
+        for (b, b_cells) in &refs {
+            assert_eq!(
+                a.contains(b),
+                b_cells.is_subset(a_cells),
+                "`{a}`.contains(`{b}`)"
+            );
+        }
+    }
+}
+
+#[test]
+fn is_same_area_matches_brute_force() {
+    let refs = references()
+        .into_iter()
+        .map(|r| {
+            let c = cells(&r);
+            (r, c)
+        })
+        .collect::<Vec<_>>();
+
+    for (a, a_cells) in &refs {
+        for (b, b_cells) in &refs {
+            assert_eq!(
+                a.is_same_area(b),
+                a_cells == b_cells,
+                "`{a}`.is_same_area(`{b}`)"
+            );
+        }
+    }
+}